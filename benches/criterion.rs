@@ -1,6 +1,9 @@
-use std::fmt;
+use std::collections::HashSet;
+use std::time::Duration;
+use std::{any, fmt};
 
 use aoc2024::*;
+use criterion::measurement::WallTime;
 use criterion::*;
 
 fn bench(criterion_manager: &mut Criterion) {
@@ -12,10 +15,142 @@ fn bench(criterion_manager: &mut Criterion) {
     run(criterion_manager).unwrap();
 }
 
-fn call_benched<In: Parse, Out: fmt::Display>(b: &mut Bencher, day: u32, f: impl FnMut(In) -> Out) {
-    let input = load_input(Mode::Private, day).unwrap();
-    let parsed: In = Parse::parse(&input);
-    b.iter_batched(|| parsed.clone(), f, BatchSize::LargeInput);
+/// An input that each variant of a day is benchmarked over.
+struct BenchInput {
+    label: String,
+    text:  String,
+    /// Whether the input is larger than real inputs,
+    /// so that slow variants take seconds per iteration on it.
+    large: bool,
+}
+
+impl BenchInput {
+    /// Configures `group` for the benchmarks on this input.
+    ///
+    /// Large inputs take 10 flat samples over 15s,
+    /// since e.g. the d6 part 2 variants take about 1.5s per iteration on them,
+    /// which criterion's default of 100 samples in 5s cannot fit.
+    /// Other inputs restore the defaults, as the group keeps its settings between inputs.
+    fn configure(&self, group: &mut BenchmarkGroup<WallTime>) {
+        group.throughput(Throughput::Bytes(self.text.len() as u64));
+        if self.large {
+            group.sample_size(10).sampling_mode(SamplingMode::Flat);
+            group.measurement_time(Duration::from_secs(15));
+        } else {
+            group.sample_size(100).sampling_mode(SamplingMode::Auto);
+            group.measurement_time(Duration::from_secs(5));
+        }
+    }
+}
+
+/// Collects the inputs of a day, skipping those that are not available,
+/// followed by generated inputs of the real size and four times larger.
+fn bench_inputs(day: u32) -> Vec<BenchInput> {
+    let mut inputs = Vec::new();
+    for &mode in Mode::ALL {
+        match load_input(mode, day) {
+            Ok(text) => {
+                inputs.push(BenchInput { label: mode.name().to_string(), text, large: false })
+            }
+            Err(err) => eprintln!("Skipping {} input of day {day}: {err:#}", mode.name()),
+        }
+    }
+
+    if let Ok(generator) = generate::find(day) {
+        for size in [generator.default_size, generator.default_size * 4] {
+            let text = generator.generate(0, size);
+            let large = size > generator.default_size;
+            inputs.push(BenchInput { label: format!("generated-{size}"), text, large });
+        }
+    }
+    inputs
+}
+
+/// Runs a solution once on an input, reporting whether it can be benchmarked on it.
+fn solves<In: Parse, Out>(
+    name: &str,
+    input: &BenchInput,
+    f: &mut impl FnMut(In) -> anyhow::Result<Out>,
+) -> bool {
+    let result = In::parse(&input.text).map_err(anyhow::Error::from).and_then(&mut *f);
+    if let Err(err) = &result {
+        eprintln!("Skipping {name} on {} input: {err:#}", input.label);
+    }
+    result.is_ok()
+}
+
+/// Benchmarks only the solve step, parsing each input in advance.
+fn call_benched<In: Parse, Out: fmt::Display>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    inputs: &[BenchInput],
+    mut f: impl FnMut(In) -> anyhow::Result<Out>,
+) {
+    for input in inputs {
+        if !solves(name, input, &mut f) {
+            continue;
+        }
+        let parsed: In = Parse::parse(&input.text).unwrap();
+        input.configure(group);
+        group.bench_with_input(BenchmarkId::new(name, &input.label), &parsed, |b, parsed| {
+            b.iter_batched(|| parsed.clone(), &mut f, BatchSize::LargeInput);
+        });
+    }
+}
+
+/// Benchmarks parsing and solving together.
+fn call_benched_e2e<In: Parse, Out: fmt::Display>(
+    group: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    inputs: &[BenchInput],
+    mut f: impl FnMut(In) -> anyhow::Result<Out>,
+) {
+    for input in inputs {
+        if !solves(name, input, &mut f) {
+            continue;
+        }
+        input.configure(group);
+        group.bench_with_input(BenchmarkId::new(name, &input.label), &input.text, |b, text| {
+            b.iter(|| f(Parse::parse(black_box(text)).unwrap()));
+        });
+    }
+}
+
+/// Benchmarks the `Parse` impl of the input type of `f` with [`bench_parse`].
+///
+/// `f` is only used to infer the type, so it is never called.
+fn bench_parse_input_of<In: Parse, Out>(
+    group: &mut BenchmarkGroup<WallTime>,
+    parsed_types: &mut HashSet<&'static str>,
+    inputs: &[BenchInput],
+    _f: impl FnOnce(In) -> Out,
+) {
+    bench_parse::<In>(group, parsed_types, inputs);
+}
+
+/// Benchmarks the `Parse` impl of `In`,
+/// unless it was already benchmarked for another variant of the day.
+fn bench_parse<In: Parse>(
+    group: &mut BenchmarkGroup<WallTime>,
+    parsed_types: &mut HashSet<&'static str>,
+    inputs: &[BenchInput],
+) {
+    let type_name = any::type_name::<In>();
+    if !parsed_types.insert(type_name) {
+        return;
+    }
+
+    let short_name = type_name.rsplit("::").next().unwrap_or(type_name);
+    for input in inputs {
+        input.configure(group);
+        group.bench_with_input(
+            BenchmarkId::new(short_name, &input.label),
+            &input.text,
+            |b, text| {
+                b.iter(|| In::parse(black_box(text)).unwrap());
+            },
+        );
+    }
 }
 
 criterion_group!(criterion, bench);
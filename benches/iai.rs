@@ -1,6 +1,57 @@
+#[cfg(feature = "iai-bench")]
+use std::{env, fmt, fs};
+
+#[cfg(feature = "iai-bench")]
+use aoc2024::*;
+
 #[cfg(feature = "iai-bench")]
 aoc2024::iai!();
 
+/// Printed before the results, since iai cannot exclude setup from the counts.
+#[cfg(feature = "iai-bench")]
+const SETUP_NOTE: &str = "Note: each count includes reading the private input and, for jq \
+                          variants, compiling the jq program, besides parsing and solving.";
+
+/// Runs the benchmarks like `iai::main!`, printing [`SETUP_NOTE`] first.
+#[cfg(feature = "iai-bench")]
+fn run_iai(benches: &[&(&'static str, fn())]) {
+    // Under cachegrind, the runner only executes the selected benchmark.
+    if env::args().nth(1).as_deref() != Some("--iai-run") {
+        println!("{SETUP_NOTE}\n");
+    }
+    iai::runner(benches);
+}
+
+/// Runs a solution on the private input of a day, skipping days whose input is not present.
+///
+/// iai counts the instructions of the whole benchmark process minus an empty calibration run,
+/// and each benchmark runs in its own process,
+/// so the input loading and jq compilation here are counted too
+/// even though they are not part of the solution.
+/// Caching them in a static would not help, since the cache would be filled in the same process.
+/// Compare counts between variants rather than reading them as the cost of a solution alone.
+///
+/// The solution is only constructed after the input is loaded,
+/// so that missing days do not compile jq programs.
+#[cfg(feature = "iai-bench")]
+fn call_iai<In: Parse, Out: fmt::Display, F: FnMut(In) -> anyhow::Result<Out>>(
+    day: u32,
+    make: impl FnOnce() -> anyhow::Result<F>,
+) {
+    let path = input_path(Mode::Private, day).unwrap();
+    if !fs::exists(&path).unwrap() {
+        eprintln!("Skipping day {day}: {} not found", path.display());
+        return;
+    }
+
+    let input = fs::read_to_string(&path).unwrap();
+    let mut f = make().unwrap();
+    let parsed: In = iai::black_box(Parse::parse(&input).unwrap());
+    if let Err(err) = iai::black_box(f(iai::black_box(parsed))) {
+        eprintln!("Day {day} failed: {err:#}");
+    }
+}
+
 #[cfg(not(feature = "iai-bench"))]
 fn main() {
     println!("iai bench skipped due to disabled feature");
@@ -0,0 +1,203 @@
+//! Collects the solution variants registered with `#[solution(...)]` in `src/all/`
+//! and generates the `macros::all!` invocation that dispatches to them.
+//!
+//! Rust variants are functions in `src/all/d{day}.rs`, or in inline modules in it,
+//! annotated with `#[solution(day = 1, part = 1, name = "zip")]`,
+//! and are registered as fallible if their return type is a `Result`.
+//! The attribute arguments are parsed by the same code as in the `solution` macro.
+//! jq variants are filters in `src/all/d{day}.jq` preceded by a
+//! `# solution(day = 1, part = 1, name = "jq")` comment line.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+#[path = "macros/src/args.rs"]
+mod args;
+
+use args::{parse_args, SolutionArgs};
+
+enum Target {
+    Rust {
+        /// Path of the function relative to the day module, e.g. `p1` or `simd::p1`.
+        fn_path:  String,
+        fallible: bool,
+    },
+    Jq(String),
+}
+
+struct Registration {
+    day:    u32,
+    part:   u32,
+    name:   String,
+    target: Target,
+}
+
+fn returns_result(output: &syn::ReturnType) -> bool {
+    let syn::ReturnType::Type(_, ty) = output else { return false };
+    let syn::Type::Path(path) = &**ty else { return false };
+    path.path.segments.last().is_some_and(|segment| segment.ident == "Result")
+}
+
+fn scan_rust(path: &Path, day: u32, registrations: &mut Vec<Registration>) {
+    let source =
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("read {}: {err}", path.display()));
+    let file =
+        syn::parse_file(&source).unwrap_or_else(|err| panic!("parse {}: {err}", path.display()));
+    scan_rust_items(path, day, &file.items, "", registrations);
+}
+
+/// Registers the annotated functions in `items`, recursing into inline modules.
+///
+/// `mod_path` is the path of `items` relative to the day module, e.g. `simd::`.
+fn scan_rust_items(
+    path: &Path,
+    day: u32,
+    items: &[syn::Item],
+    mod_path: &str,
+    registrations: &mut Vec<Registration>,
+) {
+    for item in items {
+        let item_fn = match item {
+            syn::Item::Fn(item_fn) => item_fn,
+            syn::Item::Mod(syn::ItemMod { ident, content: Some((_, items)), .. }) => {
+                scan_rust_items(path, day, items, &format!("{mod_path}{ident}::"), registrations);
+                continue;
+            }
+            _ => continue,
+        };
+        for attr in &item_fn.attrs {
+            if !attr.path().is_ident("solution") {
+                continue;
+            }
+
+            let fn_path = format!("{mod_path}{}", item_fn.sig.ident);
+            let args = attr
+                .meta
+                .require_list()
+                .and_then(|list| parse_args(list.tokens.clone()))
+                .unwrap_or_else(|err| panic!("{}: {fn_path}: {err}", path.display()));
+            assert_eq!(
+                args.day,
+                day,
+                "{}: {fn_path} is registered for day {} but defined in day {day}",
+                path.display(),
+                args.day,
+            );
+            registrations.push(Registration {
+                day,
+                part: args.part,
+                name: args.name,
+                target: Target::Rust { fn_path, fallible: returns_result(&item_fn.sig.output) },
+            });
+        }
+    }
+}
+
+fn scan_jq(path: &Path, day: u32, registrations: &mut Vec<Registration>) {
+    let source =
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("read {}: {err}", path.display()));
+
+    let mut pending: Option<(usize, SolutionArgs)> = None;
+    for (line_no, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if let Some(args) = line.strip_prefix("# solution(").and_then(|rest| rest.strip_suffix(')'))
+        {
+            let args = args
+                .parse()
+                .map_err(syn::Error::from)
+                .and_then(parse_args)
+                .unwrap_or_else(|err| panic!("{}:{}: {err}", path.display(), line_no + 1));
+            pending = Some((line_no, args));
+        } else if let Some(def) = line.strip_prefix("def ") {
+            let Some((line_no, args)) = pending.take() else { continue };
+            let filter = def.split([':', '(']).next().unwrap_or_default().trim();
+            assert!(
+                def[filter.len()..].trim_start().starts_with(':'),
+                "{}:{}: solution filter {filter} must not take arguments",
+                path.display(),
+                line_no + 1,
+            );
+            assert_eq!(
+                args.day,
+                day,
+                "{}:{}: {filter} is registered for day {} but defined in day {day}",
+                path.display(),
+                line_no + 1,
+                args.day,
+            );
+            registrations.push(Registration {
+                day,
+                part: args.part,
+                name: args.name,
+                target: Target::Jq(filter.to_string()),
+            });
+        }
+    }
+
+    if let Some((line_no, _)) = pending {
+        panic!("{}:{}: solution comment is not followed by a filter", path.display(), line_no + 1);
+    }
+}
+
+fn main() {
+    let all_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/all");
+    println!("cargo::rerun-if-changed={}", all_dir.display());
+
+    let mut registrations = Vec::new();
+    let entries =
+        fs::read_dir(&all_dir).unwrap_or_else(|err| panic!("read {}: {err}", all_dir.display()));
+    let mut paths: Vec<PathBuf> = entries.map(|entry| entry.unwrap().path()).collect();
+    paths.sort();
+    for path in &paths {
+        let Some(day) =
+            path.file_stem().and_then(|stem| stem.to_str()?.strip_prefix('d')?.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("rs") => scan_rust(path, day, &mut registrations),
+            Some("jq") => scan_jq(path, day, &mut registrations),
+            _ => {}
+        }
+    }
+
+    // Rust variants precede jq variants within a part, each in order of definition.
+    registrations.sort_by_key(|reg| (reg.day, reg.part, matches!(reg.target, Target::Jq(_))));
+
+    let mut output = String::from("macros::all! {\n");
+    for (index, reg) in registrations.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| &registrations[index]);
+        if previous.is_none_or(|previous| previous.day != reg.day) {
+            writeln!(output, "    day {} {{", reg.day).unwrap();
+        }
+        if previous.is_none_or(|previous| (previous.day, previous.part) != (reg.day, reg.part)) {
+            writeln!(output, "        part {} {{", reg.part).unwrap();
+        }
+
+        let name = &reg.name;
+        match &reg.target {
+            Target::Rust { fn_path, fallible: false } => {
+                writeln!(output, "            {name:?} => {fn_path},")
+            }
+            Target::Rust { fn_path, fallible: true } => {
+                writeln!(output, "            {name:?} => {fn_path}?,")
+            }
+            Target::Jq(filter) => writeln!(output, "            {name:?} => jq[{filter:?}],"),
+        }
+        .unwrap();
+
+        let next = registrations.get(index + 1);
+        if next.is_none_or(|next| (next.day, next.part) != (reg.day, reg.part)) {
+            output.push_str("        }\n");
+        }
+        if next.is_none_or(|next| next.day != reg.day) {
+            output.push_str("    }\n");
+        }
+    }
+    output.push_str("}\n");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(&out_path, output)
+        .unwrap_or_else(|err| panic!("write {}: {err}", out_path.display()));
+}
@@ -0,0 +1,5 @@
+#![no_main]
+
+use aoc2024::all::d5::Input;
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| aoc2024_fuzz::parse::<Input>(data));
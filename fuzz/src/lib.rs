@@ -0,0 +1,31 @@
+//! Shared entry points of the fuzz targets.
+//!
+//! `parse_*` targets only exercise a `Parse` impl,
+//! while `solve_d{day}` targets run every Rust variant of a day on inputs that parse.
+//! jq variants are excluded since the jq interpreter is not instrumented for coverage.
+//!
+//! Crash inputs are minimized and kept in `fuzz/regressions/{target}/` until they are fixed,
+//! and can be replayed by passing them to the target:
+//!
+//! ```sh
+//! cargo +nightly fuzz run solve_d7
+//! cargo +nightly fuzz tmin solve_d7 fuzz/artifacts/solve_d7/crash-...
+//! cargo +nightly fuzz run solve_d7 fuzz/regressions/solve_d7/*
+//! ```
+
+use aoc2024::all::{Backend, SOLUTIONS};
+use aoc2024::{Parse, Repetition};
+
+pub fn parse<T: Parse>(data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        _ = T::parse(input);
+    }
+}
+
+pub fn solve(day: u32, data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else { return };
+
+    for soln in SOLUTIONS.iter().filter(|soln| soln.day == day && soln.backend == Backend::Rust) {
+        _ = soln.solve(input, Repetition::ONCE);
+    }
+}
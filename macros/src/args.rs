@@ -0,0 +1,39 @@
+//! Arguments of `#[solution(day = 1, part = 1, name = "zip")]`.
+//!
+//! This file is also included by the build script with `#[path]`,
+//! so that the attribute and the registration scan accept the same arguments.
+
+use syn::punctuated::Punctuated;
+
+#[allow(dead_code)] // only read by the build script
+pub struct SolutionArgs {
+    pub day:  u32,
+    pub part: u32,
+    pub name: String,
+}
+
+/// Parses `day`, `part` and `name` in any order.
+pub fn parse_args(tokens: proc_macro2::TokenStream) -> syn::Result<SolutionArgs> {
+    let pairs = syn::parse::Parser::parse2(
+        Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated,
+        tokens.clone(),
+    )?;
+
+    let (mut day, mut part, mut name) = (None, None, None);
+    for pair in pairs {
+        let syn::Expr::Lit(syn::ExprLit { lit, .. }) = &pair.value else {
+            return Err(syn::Error::new_spanned(&pair.value, "expected a literal"));
+        };
+        match (pair.path.get_ident().map(|ident| ident.to_string()).as_deref(), lit) {
+            (Some("day"), syn::Lit::Int(lit)) => day = Some(lit.base10_parse()?),
+            (Some("part"), syn::Lit::Int(lit)) => part = Some(lit.base10_parse()?),
+            (Some("name"), syn::Lit::Str(lit)) => name = Some(lit.value()),
+            _ => return Err(syn::Error::new_spanned(&pair, "unexpected solution argument")),
+        }
+    }
+
+    match (day, part, name) {
+        (Some(day), Some(part), Some(name)) => Ok(SolutionArgs { day, part, name }),
+        _ => Err(syn::Error::new_spanned(tokens, "expected `day = _, part = _, name = \"_\"`")),
+    }
+}
@@ -1,24 +1,45 @@
 use std::env;
 use std::path::PathBuf;
 
-use itertools::Itertools;
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
+mod args;
+
 #[proc_macro]
 pub fn all(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
     all_impl(ts.into()).unwrap_or_else(syn::Error::into_compile_error).into()
 }
 
+/// Registers a function as a solution variant, e.g. `#[solution(day = 6, part = 1, name = "bitvec")]`.
+///
+/// The arguments may appear in any order.
+/// The function returns either `impl Display` or `anyhow::Result<impl Display>`.
+/// The registrations are collected by the build script, which generates the `all!` invocation.
+/// The attribute itself only validates its arguments and leaves the function unchanged.
+#[proc_macro_attribute]
+pub fn solution(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    solution_impl(attr.into(), item.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
 mod kw {
     syn::custom_keyword!(day);
     syn::custom_keyword!(part);
     syn::custom_keyword!(jq);
 }
 
+fn solution_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    args::parse_args(attr)?;
+    let item_fn: syn::ItemFn = syn::parse2(item)?;
+    Ok(quote!(#item_fn))
+}
+
 struct Input {
     days: Vec<Day>,
 }
@@ -63,7 +84,7 @@ impl Parse for Day {
 }
 
 struct Part {
-    part_token:    kw::part,
+    _part_token:   kw::part,
     part_number:   syn::LitInt,
     _braces_token: syn::token::Brace,
     solutions:     Punctuated<Solution, syn::Token![,]>,
@@ -79,7 +100,7 @@ impl Parse for Part {
 
         let solutions = Punctuated::parse_terminated(&inner)?;
 
-        Ok(Self { part_token, part_number, _braces_token: braces_token, solutions })
+        Ok(Self { _part_token: part_token, part_number, _braces_token: braces_token, solutions })
     }
 }
 
@@ -102,11 +123,22 @@ enum SolutionTarget {
         filter_ident:    syn::LitStr,
     },
     Rust {
-        fn_ident: syn::Ident,
+        /// Path of the function relative to the day module, e.g. `p1` or `simd::p1`.
+        fn_path:  syn::Path,
+        /// Present if the function returns `anyhow::Result`, as in `"name" => p1_fallible?`.
+        fallible: Option<syn::Token![?]>,
     },
 }
 
 impl SolutionTarget {
+    fn backend(&self) -> TokenStream {
+        match self {
+            Self::Jq { .. } => quote!(Backend::Jq),
+            Self::Rust { .. } => quote!(Backend::Rust),
+        }
+    }
+
+    /// Returns an expression of a `FnMut(In) -> anyhow::Result<Out>` that runs the solution.
     fn fn_expr(&self, day: &Day, all_module_path: TokenStream) -> syn::Result<TokenStream> {
         Ok(match self {
             Self::Jq { filter_ident, .. } => {
@@ -119,17 +151,44 @@ impl SolutionTarget {
                     let mut program =
                         jq_rs::compile(concat!(include_str!(#file_path), "\n", #filter_ident))
                             .map_err(|err| anyhow::anyhow!("compile d{}.jq: {err}", #day_number))?;
-                    move |data: JsonString| -> String {
-                        program.run(data.0.as_str()).expect("jq program error")
+                    move |data: JsonString| -> anyhow::Result<String> {
+                        program.run(data.0.as_str()).map_err(|err| anyhow::anyhow!("jq: {err}"))
                     }
                 }}
             }
-            Self::Rust { fn_ident } => {
+            Self::Rust { fn_path, fallible: Some(_) } => {
+                let day_ident = day.mod_ident();
+                quote!(#all_module_path #day_ident::#fn_path)
+            }
+            Self::Rust { fn_path, fallible: None } => {
                 let day_ident = day.mod_ident();
-                quote!(#all_module_path #day_ident::#fn_ident)
+                quote!(|input| anyhow::Ok(#all_module_path #day_ident::#fn_path(input)))
             }
         })
     }
+
+    /// Returns a call that benchmarks parsing the input type of the solution.
+    ///
+    /// The type is named directly for jq and taken from the function item for Rust,
+    /// so that no solution is constructed and no jq program is compiled for it.
+    fn bench_parse_call(&self, day: &Day, all_module_path: TokenStream) -> TokenStream {
+        match self {
+            Self::Jq { .. } => quote! {
+                bench_parse::<aoc2024::JsonString>(&mut group, &mut parsed_types, &inputs)
+            },
+            Self::Rust { fn_path, .. } => {
+                let day_ident = day.mod_ident();
+                quote! {
+                    bench_parse_input_of(
+                        &mut group,
+                        &mut parsed_types,
+                        &inputs,
+                        #all_module_path #day_ident::#fn_path,
+                    )
+                }
+            }
+        }
+    }
 }
 
 impl Parse for SolutionTarget {
@@ -142,7 +201,7 @@ impl Parse for SolutionTarget {
             let filter_ident = inner.parse()?;
             Ok(Self::Jq { _jq_token: jq_token, _brackets_token: brackets_token, filter_ident })
         } else if lh.peek(syn::Ident) {
-            Ok(Self::Rust { fn_ident: input.parse()? })
+            Ok(Self::Rust { fn_path: input.parse()?, fallible: input.parse()? })
         } else {
             Err(lh.error())
         }
@@ -152,174 +211,201 @@ impl Parse for SolutionTarget {
 fn all_impl(ts: TokenStream) -> syn::Result<TokenStream> {
     let Input { days } = syn::parse2(ts)?;
 
-    let mods = days.iter().map(|day| {
-        let day_ident = day.mod_ident();
-        quote_spanned! { day.day_token.span =>
-            pub mod #day_ident;
-        }
+    // The invocation is generated into OUT_DIR, so module paths must be absolute.
+    let mods = days
+        .iter()
+        .map(|day| {
+            let day_ident = day.mod_ident();
+            let file_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+                .join(format!("src/all/d{}.rs", day.day_number.base10_parse::<u32>()?));
+            let file_path =
+                file_path.to_str().expect("build path must not contain non-unicode characters");
+            Ok(quote_spanned! { day.day_token.span =>
+                #[path = #file_path]
+                pub mod #day_ident;
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let descriptors = days
+        .iter()
+        .flat_map(|day| {
+            let day_int = &day.day_number;
+            day.parts.iter().flat_map(move |part| {
+                let part_int = &part.part_number;
+                part.solutions.iter().map(move |solution| {
+                    let solution_name = &solution.name;
+                    let backend = solution.target.backend();
+                    let solution_fn_expr = solution.target.fn_expr(day, quote!())?;
+                    Ok(quote_spanned! { solution.arrow.span() =>
+                        SolutionDescriptor {
+                            day: #day_int,
+                            part: #part_int,
+                            name: #solution_name,
+                            backend: #backend,
+                            entry: |input, repetition| call(#solution_fn_expr, input, repetition),
+                        },
+                    })
+                })
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let tests = days.iter().flat_map(|day| {
+        let day_int = &day.day_number;
+        day.parts.iter().flat_map(move |part| {
+            let part_int = &part.part_number;
+            part.solutions.iter().map(move |solution| {
+                let solution_name = &solution.name;
+                let test_ident = syn::Ident::new(
+                    &format!(
+                        "day_{day_int}_part_{part_int}_{}",
+                        solution_name.value().replace(|ch: char| !ch.is_alphanumeric(), "_")
+                    ),
+                    solution_name.span(),
+                );
+                quote_spanned! { solution.arrow.span() =>
+                    #[test]
+                    fn #test_ident() {
+                        crate::verify::check(#day_int, #part_int, #solution_name);
+                    }
+                }
+            })
+        })
     });
 
-    let day_run_arms = days
+    let bench_groups = days
         .iter()
         .map(|day| {
-            let day_int = &day.day_number;
+            let day_number = &day.day_number;
 
-            let part_arms = day
+            // Variants sharing an input type are deduplicated at runtime by `bench_parse`.
+            let parse_group_name = format!("Day {} Parse", day.day_number);
+            let parse_functions = day
+                .parts
+                .iter()
+                .flat_map(|part| &part.solutions)
+                .map(|soln| {
+                    let bench_parse_call =
+                        soln.target.bench_parse_call(day, quote!(aoc2024::all::));
+                    quote_spanned! { soln.arrow.span() => #bench_parse_call; }
+                })
+                .collect::<Vec<_>>();
+
+            let part_groups = day
                 .parts
                 .iter()
                 .map(|part| {
-                    let part_int = &part.part_number;
-                    let solution_arms = part
+                    let rust_group_name =
+                        format!("Day {} Part {} Rust", day.day_number, part.part_number);
+                    let jq_group_name =
+                        format!("Day {} Part {} JQ", day.day_number, part.part_number);
+                    let e2e_group_name =
+                        format!("Day {} Part {} Parse + Solve", day.day_number, part.part_number);
+
+                    let functions = part
                         .solutions
                         .iter()
-                        .map(|solution| {
-                            let solution_name = &solution.name;
-                            let solution_fn_expr = solution.target.fn_expr(day, quote!())?;
-                            Ok(quote_spanned! { solution.arrow.span() =>
-                                #solution_name => call(#solution_fn_expr, &input),
-                            })
+                        .map(|soln| {
+                            let soln_name = &soln.name;
+                            let solution_fn_expr =
+                                soln.target.fn_expr(day, quote!(aoc2024::all::))?;
+                            Ok((
+                                matches!(soln.target, SolutionTarget::Jq { .. }),
+                                quote_spanned! { soln.arrow.span() =>
+                                    {
+                                        let mut f = #solution_fn_expr;
+                                        call_benched(&mut group, #soln_name, &inputs, &mut f);
+                                    }
+                                },
+                                quote_spanned! { soln.arrow.span() =>
+                                    {
+                                        let mut f = #solution_fn_expr;
+                                        call_benched_e2e(&mut group, #soln_name, &inputs, &mut f);
+                                    }
+                                },
+                            ))
                         })
                         .collect::<syn::Result<Vec<_>>>()?;
 
-                    let available_solution_names = part
-                        .solutions
-                        .iter()
-                        .map(|solution| format!("\"{}\"", solution.name.value()))
-                        .join(", ");
-
-                    Ok(quote_spanned! { part.part_token.span =>
-                        #part_int => {
-                            let input = load_input(args.mode, #day_int)?;
-                            let output = match variant {
-                                #(#solution_arms)*
-                                variant => anyhow::bail!(
-                                    "Unknown solution name {variant}. Available solutions: {}",
-                                    #available_solution_names,
-                                )
-                            };
-                            println!("Output: {output}");
-                            Ok(())
-                        },
+                    let rust_functions =
+                        functions.iter().filter_map(|&(is_jq, ref ts, _)| (!is_jq).then_some(ts));
+                    let jq_functions =
+                        functions.iter().filter_map(|&(is_jq, ref ts, _)| is_jq.then_some(ts));
+                    let e2e_functions = functions.iter().map(|(_, _, ts)| ts);
+
+                    Ok(quote! {
+                        {
+                            let mut group = $criterion_manager.benchmark_group(#rust_group_name);
+                            #(#rust_functions)*
+                            group.finish();
+                        }
+                        {
+                            let mut group = $criterion_manager.benchmark_group(#jq_group_name);
+                            #(#jq_functions)*
+                            group.finish();
+                        }
+                        {
+                            let mut group = $criterion_manager.benchmark_group(#e2e_group_name);
+                            #(#e2e_functions)*
+                            group.finish();
+                        }
                     })
                 })
                 .collect::<syn::Result<Vec<_>>>()?;
 
-            Ok(quote_spanned! { day.day_token.span =>
-                #day_int => match args.part {
-                    #(#part_arms)*
-                    part => anyhow::bail!("No solutions for part {part}"),
-                },
-            })
+            Ok(quote! {{
+                let inputs = bench_inputs(#day_number);
+                {
+                    let mut group = $criterion_manager.benchmark_group(#parse_group_name);
+                    let mut parsed_types = std::collections::HashSet::new();
+                    #(#parse_functions)*
+                    group.finish();
+                }
+                #(#part_groups)*
+            }})
         })
         .collect::<syn::Result<Vec<_>>>()?;
 
-    let bench_groups = days
+    let iai_fns = days
         .iter()
         .flat_map(|day| {
             let day_number = &day.day_number;
-
-            day.parts.iter().map(move |part| {
-                let rust_group_name =
-                    format!("Day {} Part {} Rust", day.day_number, part.part_number);
-                let jq_group_name = format!("Day {} Part {} JQ", day.day_number, part.part_number);
-
-                let functions = part
-                    .solutions
-                    .iter()
-                    .map(|soln| {
-                        let soln_name = &soln.name;
-                        let solution_fn_expr = soln.target.fn_expr(day, quote!(aoc2024::all::))?;
-                        Ok((
-                            matches!(soln.target, SolutionTarget::Jq { .. }),
-                            quote_spanned! { soln.arrow.span() =>
-                                {
-                                    let mut f = #solution_fn_expr;
-                                    group.bench_function(#soln_name, move |b| {
-                                        call_benched(b, #day_number, &mut f);
-                                    });
-                                }
-                            },
-                        ))
-                    })
-                    .collect::<syn::Result<Vec<_>>>()?;
-
-                let rust_functions =
-                    functions.iter().filter_map(|&(is_jq, ref ts)| (!is_jq).then_some(ts));
-                let jq_functions =
-                    functions.iter().filter_map(|&(is_jq, ref ts)| is_jq.then_some(ts));
-
-                Ok(quote! {
-                    {
-                        let mut group = $criterion_manager.benchmark_group(#rust_group_name);
-                        #(#rust_functions)*
-                        group.finish();
-                    }
-                    {
-                        let mut group = $criterion_manager.benchmark_group(#jq_group_name);
-                        #(#jq_functions)*
-                        group.finish();
-                    }
+            day.parts.iter().flat_map(move |part| {
+                part.solutions.iter().map(move |soln| {
+                    let fn_ident = syn::Ident::new(
+                        &format!(
+                            "day_{day_number}_part_{}_{}",
+                            part.part_number,
+                            soln.name.value().replace(|ch: char| !ch.is_alphanumeric(), "_")
+                        ),
+                        soln.name.span(),
+                    );
+                    let solution_fn_expr = soln.target.fn_expr(day, quote!(aoc2024::all::))?;
+                    let fn_def = quote_spanned! { soln.name.span() =>
+                        fn #fn_ident() {
+                            call_iai(#day_number, || Ok(#solution_fn_expr));
+                        }
+                    };
+                    Ok((fn_ident, fn_def))
                 })
             })
         })
         .collect::<syn::Result<Vec<_>>>()?;
 
-    let iai_fns = days
-        .iter()
-        .map(|day| {
-            let day_number = day.day_number.clone().base10_parse::<u32>()?;
-
-            day.parts
-                .iter()
-                .flat_map(move |part| {
-                    part.solutions
-                        .iter()
-                        .filter_map(move |soln| match &soln.target {
-                            SolutionTarget::Jq { .. } => None,
-                            target @ SolutionTarget::Rust { fn_ident } => {
-                                Some((soln.name.clone(), target, fn_ident.clone()))
-                            }
-                        })
-                        .map(move |(soln_name, soln_target, fn_ident)| {
-                            let fn_ident = syn::Ident::new(
-                                &format!("day_{day_number}_part_{}_{fn_ident}", part.part_number),
-                                soln_name.span(),
-                            );
-                            let solution_fn_expr =
-                                soln_target.fn_expr(day, quote!(aoc2024::all::))?;
-                            let file_path = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
-                                .join(format!("input/d{day_number}.private.input.txt"));
-                            let file_path = file_path
-                                .to_str()
-                                .expect("build path must not contain non-unicode characters");
-                            let fn_def = quote_spanned! { soln_name.span() =>
-                                fn #fn_ident() {
-                                    let input = include_str!(#file_path);
-                                    let parsed = iai::black_box(aoc2024::Parse::parse(input));
-                                    iai::black_box(#solution_fn_expr(iai::black_box(parsed)));
-                                }
-                            };
-                            Ok((fn_ident, fn_def))
-                        })
-                })
-                .collect::<syn::Result<Vec<_>>>()
-        })
-        .collect::<syn::Result<Vec<_>>>()?;
-
-    let iai_fn_idents =
-        iai_fns.iter().flatten().map(|(iai_fn_ident, _)| iai_fn_ident).collect::<Vec<_>>();
-    let iai_fn_defs =
-        iai_fns.iter().flatten().map(|(_, iai_fn_def)| iai_fn_def).collect::<Vec<_>>();
+    let iai_fn_idents = iai_fns.iter().map(|(iai_fn_ident, _)| iai_fn_ident);
+    let iai_fn_defs = iai_fns.iter().map(|(_, iai_fn_def)| iai_fn_def);
 
     let output = quote! {
         #(#mods)*
 
-        pub fn run(args: Args) -> anyhow::Result<()> {
-            let variant = args.variant.as_str();
-            match args.day {
-                #(#day_run_arms)*
-                day => anyhow::bail!("No solutions for day {day}"),
-            }
+        pub static SOLUTIONS: &[SolutionDescriptor] = &[
+            #(#descriptors)*
+        ];
+
+        #[cfg(test)]
+        mod tests {
+            #(#tests)*
         }
 
         #[macro_export]
@@ -333,8 +419,8 @@ fn all_impl(ts: TokenStream) -> syn::Result<TokenStream> {
         macro_rules! iai {
             () => {
                 #(#iai_fn_defs)*
-                iai::main! {
-                    #(#iai_fn_idents,)*
+                fn main() {
+                    run_iai(&[#(&(stringify!(#iai_fn_idents), #iai_fn_idents),)*]);
                 }
             }
         }
@@ -1,106 +1,237 @@
-use std::fmt;
-use std::time::Instant;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fmt, thread};
 
-use crate::{load_input, Args, JsonString, Parse};
+use anyhow::Context;
+use itertools::Itertools;
 
-fn call<In: Parse, Out: fmt::Display>(mut f: impl FnMut(In) -> Out, input: &str) -> String {
-    let start_time = Instant::now();
-    let parsed = Parse::parse(input);
-    let duration = Instant::now() - start_time;
-    eprintln!("Parse time: {}ms", duration.as_secs_f32() * 1000.);
+use crate::report::{self, OutputFormat, Record};
+use crate::stats::Samples;
+use crate::{InputSource, JsonString, Parse, Repetition, RunArgs};
 
-    let start_time = Instant::now();
-    let output = f(parsed);
-    let duration = Instant::now() - start_time;
-    eprintln!("Execution time: {}ms", duration.as_secs_f32() * 1000.);
-
-    output.to_string()
+/// A registered solution variant.
+pub struct SolutionDescriptor {
+    pub day:     u32,
+    pub part:    u32,
+    pub name:    &'static str,
+    pub backend: Backend,
+    /// Parses the input and runs the solution on it.
+    pub entry:   fn(&str, Repetition) -> anyhow::Result<Outcome>,
 }
 
-macros::all! {
-    day 1 {
-        part 1 {
-            "zip" => p1_zip,
-            "jq" => jq["d1q1"],
-        }
-        part 2 {
-            "hash" => p2_hash,
-            "sorted" => p2_sorted,
-            "count" => p2_count,
-            "bitvec" => p2_bitvec,
-            "jq/hash" => jq["d1q2_hash"],
-        }
+impl SolutionDescriptor {
+    pub fn solve(&self, input: &str, repetition: Repetition) -> anyhow::Result<Outcome> {
+        (self.entry)(input, repetition)
     }
-    day 2 {
-        part 1 {
-            "windows" => p1_windows,
-            "first-all" => p1_first_all,
-            "jq" => jq["d2q1"],
-        }
-        part 2 {
-            "brute" => p2_brute_force,
-            "vec" => p2_vec,
-            "jq" => jq["d2q2"],
+
+    /// Solves on a worker thread, reporting panics and timeouts as errors.
+    ///
+    /// A variant that times out cannot be interrupted,
+    /// so it keeps running in the background until it finishes or the process exits.
+    pub fn solve_isolated(
+        &'static self,
+        input: Arc<str>,
+        repetition: Repetition,
+        timeout: Duration,
+    ) -> anyhow::Result<Outcome> {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name(format!("day {} part {} {}", self.day, self.part, self.name))
+            .spawn(move || {
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| self.solve(&input, repetition)));
+                _ = sender.send(result);
+            })
+            .context("spawn solution worker")?;
+
+        match receiver.recv_timeout(timeout) {
+            Ok(Ok(result)) => result,
+            Ok(Err(payload)) => anyhow::bail!("panicked: {}", panic_message(&*payload)),
+            Err(RecvTimeoutError::Timeout) => Err(TimedOut(timeout).into()),
+            Err(RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("solution worker exited without a result")
+            }
         }
     }
-    day 3 {
-        part 1 {
-            "find" => p1_find,
-            "jq" => jq["d3q1"],
-        }
-        part 2 {
-            "find" => p2_find,
-            "jq" => jq["d3q2"],
-        }
+}
+
+/// The error of a variant that did not finish within the timeout of
+/// [`SolutionDescriptor::solve_isolated`].
+#[derive(Debug)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {}s", self.0.as_secs())
     }
-    day 4 {
-        part 1 {
-            "brute" => p1_brute,
-        }
-        part 2 {
-            "brute" => p2_brute,
-        }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Warns that timed-out variants are still running in the background,
+/// competing for the CPU with the variants that were timed after them.
+pub(crate) fn warn_timeouts(count: usize) {
+    if count > 0 {
+        eprintln!(
+            "Warning: {count} variants timed out and kept running in the background, so the times \
+             of the variants run after them may be inflated"
+        );
     }
-    day 5 {
-        part 1 {
-            "fxhashmap-fxhashset" => p1_fxhashmap_fxhashset,
-            "btreemap-fxhashset" => p1_btreemap_fxhashset,
-            "fxhashmap-vec" => p1_fxhashmap_vec,
-            "btreemap-vec" => p1_btreemap_vec,
-        }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic payload"
     }
-    day 6 {
-        part 1 {
-            "ticked-fxhash-loc" => p1_ticked_fxhash_loc,
-            "ticked-fxhash-index" => p1_ticked_fxhash_index,
-            "ticked-boolvec" => p1_ticked_boolvec,
-            "ticked-bitvec" => p1_ticked_bitvec,
-        }
-        part 2 {
-            "brute-fxhash-loc" => p2_brute_fxhash_loc,
-        }
+}
+
+/// Finds a registered solution variant.
+pub fn find(day: u32, part: u32, name: &str) -> anyhow::Result<&'static SolutionDescriptor> {
+    if !SOLUTIONS.iter().any(|soln| soln.day == day) {
+        anyhow::bail!("No solutions for day {day}");
     }
-    day 7 {
-        part 1 {
-            "reversed" => p1_reversed,
-            "jq" => jq["d7q1"],
-        }
-        part 2 {
-            "reversed" => p2_reversed,
-            "jq" => jq["d7q2"],
-        }
+
+    let mut part_solutions =
+        SOLUTIONS.iter().filter(|soln| soln.day == day && soln.part == part).peekable();
+    if part_solutions.peek().is_none() {
+        anyhow::bail!("No solutions for part {part}");
     }
-    day 8 {
-        part 1 {
-            "naive" => p1_naive,
-        }
-        part 2 {
-            "naive" => p2_naive,
+
+    let available = part_solutions.clone().map(|soln| format!("\"{}\"", soln.name)).join(", ");
+    part_solutions
+        .find(|soln| soln.name == name)
+        .with_context(|| format!("Unknown solution name {name}. Available solutions: {available}"))
+}
+
+pub fn solve(
+    day: u32,
+    part: u32,
+    name: &str,
+    input: &str,
+    repetition: Repetition,
+) -> anyhow::Result<Outcome> {
+    find(day, part, name)?.solve(input, repetition)
+}
+
+/// The language a solution variant is implemented in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Rust,
+    Jq,
+}
+
+impl Backend {
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Rust => "Rust",
+            Backend::Jq => "jq",
         }
     }
-    day 9 {
-        part 1 {
-            "chunk-iter" => p1_chunk_iter,
+}
+
+/// The result of running a solution on an input.
+pub struct Outcome {
+    pub parse_times: Samples,
+    pub exec_times:  Samples,
+    pub output:      String,
+}
+
+fn call<In: Parse, Out: fmt::Display>(
+    mut f: impl FnMut(In) -> anyhow::Result<Out>,
+    input: &str,
+    repetition: Repetition,
+) -> anyhow::Result<Outcome> {
+    for _ in 0..repetition.warmup {
+        f(Parse::parse(input).context("parse input")?).context("solve")?;
+    }
+
+    let mut parse_times = Vec::new();
+    let mut exec_times = Vec::new();
+    let mut output = None;
+
+    for _ in 0..repetition.repeat.max(1) {
+        let start_time = Instant::now();
+        let parsed = Parse::parse(input).context("parse input")?;
+        parse_times.push(Instant::now() - start_time);
+
+        let start_time = Instant::now();
+        let run_output = f(parsed).context("solve")?;
+        exec_times.push(Instant::now() - start_time);
+
+        output = Some(run_output);
+    }
+
+    Ok(Outcome {
+        parse_times: Samples::new(parse_times),
+        exec_times:  Samples::new(exec_times),
+        output:      output.expect("at least one repetition").to_string(),
+    })
+}
+
+pub fn run(args: RunArgs) -> anyhow::Result<()> {
+    let source = InputSource::new(args.mode, args.input);
+
+    if let (OutputFormat::Text, Some(day), Some(part), Some(variant)) =
+        (args.output_format, args.day, args.part, &args.variant)
+    {
+        return run_one(&source, day, part, variant, args.repetition);
+    }
+
+    let selected: Vec<&SolutionDescriptor> = SOLUTIONS
+        .iter()
+        .filter(|variant| args.day.is_none_or(|day| variant.day == day))
+        .filter(|variant| args.part.is_none_or(|part| variant.part == part))
+        .filter(|variant| args.variant.as_ref().is_none_or(|name| variant.name == name))
+        .collect();
+    if selected.is_empty() {
+        anyhow::bail!("No solutions match the given filters");
+    }
+
+    let timeout = Duration::from_secs(args.timeout);
+    let mut records = Vec::new();
+    for (day, variants) in &selected.into_iter().chunk_by(|variant| variant.day) {
+        let input = source.load(day).map(Arc::<str>::from);
+
+        for variant in variants {
+            let result = input
+                .as_ref()
+                .map_err(|err| anyhow::anyhow!("{err:#}"))
+                .and_then(|input| variant.solve_isolated(input.clone(), args.repetition, timeout));
+            records.push(Record::new(&source, variant, result));
         }
     }
+
+    report::print(args.output_format, &records)?;
+
+    let errors: Vec<_> = records.iter().filter_map(|record| record.result.as_ref().err()).collect();
+    warn_timeouts(errors.iter().filter(|err| err.is::<TimedOut>()).count());
+    if !errors.is_empty() {
+        anyhow::bail!("{} variants failed", errors.len());
+    }
+    Ok(())
 }
+
+fn run_one(
+    source: &InputSource,
+    day: u32,
+    part: u32,
+    variant: &str,
+    repetition: Repetition,
+) -> anyhow::Result<()> {
+    let solution = find(day, part, variant)?;
+    let input = source.load(day)?;
+    let outcome = solution.solve(&input, repetition)?;
+    eprintln!("Parse time: {}", outcome.parse_times);
+    eprintln!("Execution time: {}", outcome.exec_times);
+    println!("Output: {}", outcome.output);
+    Ok(())
+}
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
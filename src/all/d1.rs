@@ -2,8 +2,9 @@ use std::collections::HashMap;
 use std::{fmt, iter};
 
 use bitvec::vec::BitVec;
+use macros::solution;
 
-use crate::Parse;
+use crate::{Parse, ParseError};
 
 #[derive(Clone)]
 pub struct Input {
@@ -12,19 +13,21 @@ pub struct Input {
 }
 
 impl Parse for Input {
-    fn parse(input: &str) -> Self {
-        let (left, right): (Vec<_>, Vec<_>) = input
-            .lines()
-            .filter_map(|line| {
-                line.split_once(' ').map(|(left, right)| {
-                    (left.parse::<u32>().unwrap(), right.trim_ascii_start().parse::<u32>().unwrap())
-                })
-            })
-            .unzip();
-        Self { left, right }
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (left_str, right_str) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::at(input, line, "expected `left   right`"))?;
+            left.push(ParseError::parse_token(input, left_str)?);
+            right.push(ParseError::parse_token(input, right_str.trim_ascii_start())?);
+        }
+        Ok(Self { left, right })
     }
 }
 
+#[solution(day = 1, part = 1, name = "zip")]
 pub fn p1_zip(Input { mut left, mut right }: Input) -> impl fmt::Display {
     left.sort_unstable();
     right.sort_unstable();
@@ -69,6 +72,7 @@ fn unique<T: Copy + Eq>(iter: impl IntoIterator<Item = T>) -> impl Iterator<Item
     UniqueIterator { iter: iter.into_iter(), peek: None }
 }
 
+#[solution(day = 1, part = 2, name = "hash")]
 pub fn p2_hash(Input { left, right }: Input) -> impl fmt::Display {
     let mut counts = HashMap::<u32, u32>::new();
     for item in right {
@@ -78,6 +82,7 @@ pub fn p2_hash(Input { left, right }: Input) -> impl fmt::Display {
     left.into_iter().map(|item| item * counts.get(&item).copied().unwrap_or_default()).sum::<u32>()
 }
 
+#[solution(day = 1, part = 2, name = "sorted")]
 pub fn p2_sorted(Input { mut left, mut right }: Input) -> impl fmt::Display {
     left.sort_unstable();
     right.sort_unstable();
@@ -105,25 +110,41 @@ pub fn p2_sorted(Input { mut left, mut right }: Input) -> impl fmt::Display {
     output
 }
 
-pub fn p2_count(Input { left, right }: Input) -> impl fmt::Display {
-    fn collect_buckets(items: Vec<u32>) -> Vec<u32> {
-        let mut output = vec![0u32; 100000];
+/// The most buckets the counting variants allocate, 64 MiB of `u32` counts per list.
+const MAX_BUCKETS: usize = 1 << 24;
+
+/// Returns the number of buckets needed to index by every location ID,
+/// failing if an ID is too large to allocate buckets up to it.
+fn bucket_len(left: &[u32], right: &[u32]) -> anyhow::Result<usize> {
+    let len = left.iter().chain(right).max().map_or(0, |&max| max as usize + 1);
+    if len > MAX_BUCKETS {
+        anyhow::bail!("location ID {} is too large to count in buckets", len - 1);
+    }
+    Ok(len)
+}
+
+#[solution(day = 1, part = 2, name = "count")]
+pub fn p2_count(Input { left, right }: Input) -> anyhow::Result<impl fmt::Display> {
+    fn collect_buckets(items: Vec<u32>, len: usize) -> Vec<u32> {
+        let mut output = vec![0u32; len];
         for item in items {
             output[item as usize] += 1;
         }
         output
     }
 
-    let left = collect_buckets(left);
-    let right = collect_buckets(right);
+    let len = bucket_len(&left, &right)?;
+    let left = collect_buckets(left, len);
+    let right = collect_buckets(right, len);
 
-    iter::zip(left, right).enumerate().map(|(i, (l, r))| (i as u32) * l * r).sum::<u32>()
+    Ok(iter::zip(left, right).enumerate().map(|(i, (l, r))| (i as u32) * l * r).sum::<u32>())
 }
 
-pub fn p2_bitvec(Input { left, right }: Input) -> impl fmt::Display {
-    fn collect_buckets(items: Vec<u32>) -> (BitVec, Vec<u32>) {
-        let mut presence: BitVec = iter::repeat(false).take(100000).collect();
-        let mut output = vec![0u32; 100000];
+#[solution(day = 1, part = 2, name = "bitvec")]
+pub fn p2_bitvec(Input { left, right }: Input) -> anyhow::Result<impl fmt::Display> {
+    fn collect_buckets(items: Vec<u32>, len: usize) -> (BitVec, Vec<u32>) {
+        let mut presence: BitVec = iter::repeat(false).take(len).collect();
+        let mut output = vec![0u32; len];
         for item in items {
             presence.set(item as usize, true);
             output[item as usize] += 1;
@@ -131,10 +152,11 @@ pub fn p2_bitvec(Input { left, right }: Input) -> impl fmt::Display {
         (presence, output)
     }
 
-    let (left_presence, left) = collect_buckets(left);
-    let (right_presence, right) = collect_buckets(right);
+    let len = bucket_len(&left, &right)?;
+    let (left_presence, left) = collect_buckets(left, len);
+    let (right_presence, right) = collect_buckets(right, len);
 
     let presence = left_presence & right_presence;
 
-    presence.iter_ones().map(|i| left[i] * right[i] * (i as u32)).sum::<u32>()
+    Ok(presence.iter_ones().map(|i| left[i] * right[i] * (i as u32)).sum::<u32>())
 }
@@ -1,8 +1,9 @@
-use std::{cmp, fmt};
+use std::fmt;
 
 use itertools::Itertools;
+use macros::solution;
 
-use crate::Parse;
+use crate::{Parse, ParseError};
 
 #[derive(Clone)]
 pub struct Input(Vec<Line>);
@@ -11,13 +12,19 @@ pub struct Input(Vec<Line>);
 struct Line(Vec<u32>);
 
 impl Parse for Input {
-    fn parse(input: &str) -> Self {
-        Self(
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self(
             input
                 .lines()
-                .map(|line| Line(line.split(' ').map(|s| s.parse::<u32>().unwrap()).collect()))
-                .collect(),
-        )
+                .map(|line| {
+                    Ok(Line(
+                        line.split(' ')
+                            .map(|s| ParseError::parse_token::<u32>(input, s))
+                            .collect::<Result<_, _>>()?,
+                    ))
+                })
+                .collect::<Result<_, ParseError>>()?,
+        ))
     }
 }
 
@@ -49,18 +56,21 @@ fn is_safe_first_all(levels: impl Iterator<Item = u32>) -> bool {
     first != Direction::Illegal && directions.all(|d| d == first)
 }
 
+#[solution(day = 2, part = 1, name = "windows")]
 pub fn p1_windows(input: Input) -> impl fmt::Display {
     fn is_safe(line: &Line) -> bool { is_safe_windows(line.0.iter().copied()) }
 
     input.0.iter().filter(|line| is_safe(line)).count()
 }
 
+#[solution(day = 2, part = 1, name = "first-all")]
 pub fn p1_first_all(input: Input) -> impl fmt::Display {
     fn is_safe(line: &Line) -> bool { is_safe_first_all(line.0.iter().copied()) }
 
     input.0.iter().filter(|line| is_safe(line)).count()
 }
 
+#[solution(day = 2, part = 2, name = "brute")]
 pub fn p2_brute_force(input: Input) -> impl fmt::Display {
     fn is_safe(line: &Line) -> bool {
         is_safe_first_all(line.0.iter().copied())
@@ -74,48 +84,25 @@ pub fn p2_brute_force(input: Input) -> impl fmt::Display {
     input.0.iter().filter(|line| is_safe(line)).count()
 }
 
-// TODO: this answer is wrong
+#[solution(day = 2, part = 2, name = "vec")]
 pub fn p2_vec(input: Input) -> impl fmt::Display {
-    fn is_safe_skip(line: &Line, index: usize, dominant: Direction) -> bool {
-        if let (Some(prev_index), Some(&next_level)) = (index.checked_sub(1), line.0.get(index + 1))
-        {
-            if compare(line.0[prev_index], next_level) != dominant {
-                return false;
-            }
-        }
-
-        true
+    fn is_safe_skip(line: &Line, skip: usize, dominant: Direction) -> bool {
+        let levels = line.0[..skip].iter().chain(&line.0[skip + 1..]);
+        levels.tuple_windows().all(|(&left, &right)| compare(left, right) == dominant)
     }
 
     fn is_safe(line: &Line) -> bool {
         let directions: Vec<_> =
             line.0.iter().tuple_windows().map(|(&left, &right)| compare(left, right)).collect();
 
-        let increase_count = directions.iter().filter(|&&d| d == Direction::Increase).count();
-        let dominants = match (increase_count * 2).cmp(&directions.len()) {
-            cmp::Ordering::Greater => &[Direction::Increase][..],
-            cmp::Ordering::Equal => &[Direction::Increase, Direction::Decrease][..],
-            cmp::Ordering::Less => &[Direction::Decrease][..],
-        };
-
-        dominants.iter().any(|&dominant| {
-            let mut violations = directions.iter().enumerate().filter(|(_, &d)| d != dominant);
-            if let Some((index, _)) = violations.next() {
+        [Direction::Increase, Direction::Decrease].into_iter().any(|dominant| {
+            match directions.iter().position(|&d| d != dominant) {
                 // line[index] -> line[index+1] violation,
-                // attempt to skip either line[index] or line[index+1]
-
-                if is_safe_skip(line, index, dominant) && is_safe_skip(line, index + 1, dominant) {
-                    let mut next_violation = violations.next();
-                    if next_violation.is_some_and(|(next_index, _)| next_index == index + 1) {
-                        next_violation = violations.next();
-                    }
-
-                    next_violation.is_none()
-                } else {
-                    false
+                // so either line[index] or line[index+1] must be skipped
+                Some(index) => {
+                    is_safe_skip(line, index, dominant) || is_safe_skip(line, index + 1, dominant)
                 }
-            } else {
-                true
+                None => true,
             }
         })
     }
@@ -1,5 +1,8 @@
+use macros::solution;
+
 type Sum = u32;
 
+#[solution(day = 3, part = 1, name = "find")]
 pub fn p1_find(input: String) -> Sum {
     let mut input = input.as_str();
 
@@ -19,6 +22,7 @@ pub fn p1_find(input: String) -> Sum {
     sum
 }
 
+#[solution(day = 3, part = 2, name = "find")]
 pub fn p2_find(input: String) -> Sum {
     enum Mode {
         Do,
@@ -1,7 +1,10 @@
 use std::array;
 
+use macros::solution;
+
 use crate::util::{Direct, DirectBoth, DirectDiagonal, GridView};
 
+#[solution(day = 4, part = 1, name = "brute")]
 pub fn p1_brute(input: String) -> u32 {
     let grid = GridView::new(&input);
 
@@ -21,6 +24,7 @@ pub fn p1_brute(input: String) -> u32 {
     count
 }
 
+#[solution(day = 4, part = 2, name = "brute")]
 pub fn p2_brute(input: String) -> u32 {
     let grid = GridView::new(&input);
 
@@ -1,9 +1,10 @@
 use std::collections::{BTreeSet, HashSet};
 use std::hash::BuildHasher;
 
+use macros::solution;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::Parse;
+use crate::{Parse, ParseError};
 
 #[derive(Clone)]
 pub struct Input {
@@ -21,7 +22,7 @@ struct Constraint {
 struct Update(Vec<u32>);
 
 impl Parse for Input {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, ParseError> {
         let mut lines = input.lines();
 
         let mut constraints = Vec::new();
@@ -32,10 +33,12 @@ impl Parse for Input {
                 break;
             }
 
-            let (left, right) = line.split_once('|').unwrap();
+            let (left, right) = line
+                .split_once('|')
+                .ok_or_else(|| ParseError::at(input, line, "expected `earlier|later`"))?;
             constraints.push(Constraint {
-                earlier: left.parse().unwrap(),
-                later:   right.parse().unwrap(),
+                earlier: ParseError::parse_token(input, left)?,
+                later:   ParseError::parse_token(input, right)?,
             });
         }
 
@@ -44,10 +47,14 @@ impl Parse for Input {
                 break;
             }
 
-            updates.push(Update(line.split(',').map(|s| s.parse().unwrap()).collect()));
+            updates.push(Update(
+                line.split(',')
+                    .map(|s| ParseError::parse_token(input, s))
+                    .collect::<Result<_, _>>()?,
+            ));
         }
 
-        Self { constraints, updates }
+        Ok(Self { constraints, updates })
     }
 }
 
@@ -126,12 +133,16 @@ fn p1<DenyListsT: DenyLists, DisallowedSetT: DisallowedSet>(input: Input) -> u32
     result
 }
 
+#[solution(day = 5, part = 1, name = "fxhashmap-fxhashset")]
 pub fn p1_fxhashmap_fxhashset(input: Input) -> u32 {
     p1::<FxHashMapDenyLists, FxHashSet<u32>>(input)
 }
 
+#[solution(day = 5, part = 1, name = "btreemap-fxhashset")]
 pub fn p1_btreemap_fxhashset(input: Input) -> u32 { p1::<BTreeSetDenyLists, FxHashSet<u32>>(input) }
 
+#[solution(day = 5, part = 1, name = "fxhashmap-vec")]
 pub fn p1_fxhashmap_vec(input: Input) -> u32 { p1::<FxHashMapDenyLists, Vec<u32>>(input) }
 
+#[solution(day = 5, part = 1, name = "btreemap-vec")]
 pub fn p1_btreemap_vec(input: Input) -> u32 { p1::<BTreeSetDenyLists, Vec<u32>>(input) }
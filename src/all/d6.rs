@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 use std::hash::BuildHasher;
 
+use anyhow::Context;
 use bitvec::vec::BitVec;
+use macros::solution;
 use rustc_hash::FxHashSet;
 
 use crate::util::{DirectTaxicab, GridLoc, GridView};
@@ -12,10 +14,23 @@ trait LocCounter {
     fn count(&self) -> u32;
 }
 
-fn p1_ticked<CollectorT: LocCounter>(input: String) -> u32 {
-    let grid = GridView::new(&input);
+/// Checks that the map has at least one full row before viewing it as a grid.
+fn parse_grid<Input: AsRef<[u8]>>(input: Input) -> anyhow::Result<GridView<Input>> {
+    if !input.as_ref().contains(&b'\n') {
+        anyhow::bail!("map has no line break");
+    }
+    Ok(GridView::new(input))
+}
+
+fn find_guard(input: &str, grid: &GridView<impl AsRef<[u8]>>) -> anyhow::Result<GridLoc> {
+    let index = input.find('^').context("no guard in map")?;
+    grid.shape.index_to_loc(index).context("guard is outside the map")
+}
+
+fn p1_ticked<CollectorT: LocCounter>(input: String) -> anyhow::Result<u32> {
+    let grid = parse_grid(&input)?;
 
-    let mut loc = grid.shape.index_to_loc(input.find('^').unwrap()).unwrap();
+    let mut loc = find_guard(&input, &grid)?;
     let mut direct = DirectTaxicab::Up;
 
     let mut collector = CollectorT::new(input.len());
@@ -24,7 +39,7 @@ fn p1_ticked<CollectorT: LocCounter>(input: String) -> u32 {
 
         'directs: loop {
             match loc.direct(direct, grid) {
-                None => return collector.count(), // leave map
+                None => return Ok(collector.count()), // leave map
                 Some(new_loc) => match grid.get(new_loc).unwrap() {
                     b'^' | b'.' => {
                         loc = new_loc;
@@ -34,7 +49,7 @@ fn p1_ticked<CollectorT: LocCounter>(input: String) -> u32 {
                         direct = direct.clockwise();
                         continue 'directs;
                     }
-                    _ => unreachable!(),
+                    byte => anyhow::bail!("unexpected {:?} in map", char::from(byte)),
                 },
             }
         }
@@ -90,10 +105,18 @@ impl LocCounter for BitVec {
     fn count(&self) -> u32 { self.count_ones() as u32 }
 }
 
-pub fn p1_ticked_fxhash_loc(input: String) -> u32 { p1_ticked::<FxHashSet<GridLoc>>(input) }
-pub fn p1_ticked_fxhash_index(input: String) -> u32 { p1_ticked::<FxHashSet<u32>>(input) }
-pub fn p1_ticked_boolvec(input: String) -> u32 { p1_ticked::<Vec<bool>>(input) }
-pub fn p1_ticked_bitvec(input: String) -> u32 { p1_ticked::<BitVec>(input) }
+#[solution(day = 6, part = 1, name = "ticked-fxhash-loc")]
+pub fn p1_ticked_fxhash_loc(input: String) -> anyhow::Result<u32> {
+    p1_ticked::<FxHashSet<GridLoc>>(input)
+}
+#[solution(day = 6, part = 1, name = "ticked-fxhash-index")]
+pub fn p1_ticked_fxhash_index(input: String) -> anyhow::Result<u32> {
+    p1_ticked::<FxHashSet<u32>>(input)
+}
+#[solution(day = 6, part = 1, name = "ticked-boolvec")]
+pub fn p1_ticked_boolvec(input: String) -> anyhow::Result<u32> { p1_ticked::<Vec<bool>>(input) }
+#[solution(day = 6, part = 1, name = "ticked-bitvec")]
+pub fn p1_ticked_bitvec(input: String) -> anyhow::Result<u32> { p1_ticked::<BitVec>(input) }
 
 trait LoopDetector {
     fn new(capacity: usize) -> Self;
@@ -117,56 +140,48 @@ fn is_looping<DetectorT: LoopDetector>(
     grid: &GridView<impl AsRef<[u8]>>,
     det: &mut DetectorT,
     initial: GridLoc,
-) -> bool {
+) -> anyhow::Result<bool> {
     det.clear();
 
     let mut loc = initial;
     let mut direct = DirectTaxicab::Up;
 
-    'ticks: loop {
+    loop {
+        // Turns are inserted too, so that a guard boxed in by the new obstruction
+        // repeats its location and direction after four turns instead of turning forever.
         if det.insert(|| loc, || grid.shape.loc_to_index(loc), direct) == IsLooped::Repeating {
-            return true;
+            return Ok(true);
         }
 
-        'directs: loop {
-            match loc.direct(direct, grid) {
-                None => return false, // leave map
-                Some(new_loc) => match grid.get(new_loc).unwrap() {
-                    b'^' | b'.' => {
-                        loc = new_loc;
-                        continue 'ticks;
-                    }
-                    b'#' => {
-                        direct = direct.clockwise();
-                        continue 'directs;
-                    }
-                    _ => unreachable!(),
-                },
-            }
+        match loc.direct(direct, grid) {
+            None => return Ok(false), // leave map
+            Some(new_loc) => match grid.get(new_loc).unwrap() {
+                b'^' | b'.' => loc = new_loc,
+                b'#' => direct = direct.clockwise(),
+                byte => anyhow::bail!("unexpected {:?} in map", char::from(byte)),
+            },
         }
     }
 }
 
-fn p2_brute<LoopDetectorT: LoopDetector>(input: String) -> u32 {
-    let initial_index = input.find('^').unwrap();
+fn p2_brute<LoopDetectorT: LoopDetector>(input: String) -> anyhow::Result<u32> {
     let size = input.len();
     let mut det = LoopDetectorT::new(size);
 
-    let input = input.into_bytes();
-    let mut grid = GridView::new(input);
-    let initial = grid.shape.index_to_loc(initial_index).unwrap();
+    let initial = find_guard(&input, &parse_grid(&input)?)?;
+    let mut grid = GridView::new(input.into_bytes());
 
     let mut count = 0;
     for index in 0..size {
         if grid.input[index] == b'.' {
             grid.input[index] = b'#';
-            if is_looping(&grid, &mut det, initial) {
+            if is_looping(&grid, &mut det, initial)? {
                 count += 1;
             }
             grid.input[index] = b'.';
         }
     }
-    count
+    Ok(count)
 }
 
 impl<S: BuildHasher + Default> LoopDetector for HashSet<(GridLoc, DirectTaxicab), S> {
@@ -188,6 +203,7 @@ impl<S: BuildHasher + Default> LoopDetector for HashSet<(GridLoc, DirectTaxicab)
     }
 }
 
-pub fn p2_brute_fxhash_loc(input: String) -> u32 {
+#[solution(day = 6, part = 2, name = "brute-fxhash-loc")]
+pub fn p2_brute_fxhash_loc(input: String) -> anyhow::Result<u32> {
     p2_brute::<FxHashSet<(GridLoc, DirectTaxicab)>>(input)
 }
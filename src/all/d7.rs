@@ -1,5 +1,7 @@
 use std::iter;
 
+use macros::solution;
+
 fn fast_parse_once(input: &[u8], delim: u8) -> Option<(&[u8], u64)> {
     let mut buf = input;
     let mut output = 0;
@@ -85,8 +87,8 @@ fn is_valid_reverse_recurse_p1<'a>(
             }
 
             // Try multiplication
-            if result % last == 0 {
-                // operands are always nonzero
+            if result.is_multiple_of(last) {
+                // a zero `last` only divides a zero result, which addition has already accepted
                 if is_valid_reverse_recurse_p1(result / last, operands) {
                     return true;
                 }
@@ -97,6 +99,7 @@ fn is_valid_reverse_recurse_p1<'a>(
     }
 }
 
+#[solution(day = 7, part = 1, name = "reversed")]
 pub fn p1_reversed(input: String) -> u64 {
     parse(&input)
         .filter(|line| is_valid_reverse_recurse_p1(line.result, line.operands_rev()))
@@ -129,8 +132,8 @@ fn is_valid_reverse_recurse_p2<'a>(
             }
 
             // Try multiplication
-            if result % last.value == 0 {
-                // no operands equal to 0
+            if result.is_multiple_of(last.value) {
+                // a zero `last` only divides a zero result, which addition has already accepted
                 if is_valid_reverse_recurse_p2(result / last.value, operands) {
                     return true;
                 }
@@ -144,13 +147,14 @@ fn is_valid_reverse_recurse_p2<'a>(
 fn strip_base10_suffix(long: u64, suffix: Operand) -> Option<u64> {
     let remain = long.wrapping_sub(suffix.value); // works on "my input"
     let unit = 10u64.pow(suffix.bytes.len() as u32);
-    if remain % unit == 0 {
+    if remain.is_multiple_of(unit) {
         Some(remain / unit)
     } else {
         None
     }
 }
 
+#[solution(day = 7, part = 2, name = "reversed")]
 pub fn p2_reversed(input: String) -> u64 {
     parse(&input)
         .filter(|line| is_valid_reverse_recurse_p2(line.result, line.operands_rev()))
@@ -1,3 +1,4 @@
+use macros::solution;
 use rustc_hash::FxHashMap;
 
 use crate::util::{GridLoc, GridView};
@@ -8,6 +9,7 @@ fn disjoint_pairs<T>(slice: &[T]) -> impl Iterator<Item = [&T; 2]> {
     })
 }
 
+#[solution(day = 8, part = 1, name = "naive")]
 pub fn p1_naive(input: String) -> u32 {
     let grid = GridView::new(input.into_bytes());
 
@@ -18,7 +20,8 @@ pub fn p1_naive(input: String) -> u32 {
         }
     }
 
-    let mut matches = 0;
+    let mut output = vec![false; grid.input.len()];
+
     for indices in freqs.values() {
         for pair_index in disjoint_pairs(indices) {
             let pair_loc =
@@ -29,18 +32,15 @@ pub fn p1_naive(input: String) -> u32 {
                 .into_iter()
                 .flatten()
             {
-                let byte = grid.get(loc).unwrap();
-                if byte == b'.' {
-                    matches += 1;
-                }
+                output[grid.shape.loc_to_index(loc) as usize] = true;
             }
         }
     }
 
-    // turns out it is the number of location-frequency pairs not unique locations???
-    matches
+    output.iter().filter(|&&b| b).count() as u32
 }
 
+#[solution(day = 8, part = 2, name = "naive")]
 pub fn p2_naive(input: String) -> u32 {
     let grid = GridView::new(input.into_bytes());
 
@@ -1,3 +1,5 @@
+use macros::solution;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct BlockCount(u32);
 
@@ -64,7 +66,7 @@ impl<'a> Iterator for ChunkIter<'a> {
             return Some(out);
         }
 
-        if self.head_ptr % 2 == 0 {
+        if self.head_ptr.is_multiple_of(2) {
             let out = (
                 FileId::from_input_index(self.head_ptr),
                 BlockCount::from_digit(self.data[self.head_ptr]),
@@ -103,6 +105,7 @@ fn sum_range(start: u32, end: u32) -> u64 {
     (end - start) * (end + start - 1) / 2
 }
 
+#[solution(day = 9, part = 1, name = "chunk-iter")]
 pub fn p1_chunk_iter(input: String) -> u64 {
     let input = input.trim_end();
 
@@ -110,7 +113,7 @@ pub fn p1_chunk_iter(input: String) -> u64 {
         data:          input.as_bytes(),
         head_ptr:      0,
         head_consumed: BlockCount(0),
-        tail_ptr:      if input.len() % 2 == 0 { input.len() - 2 } else { input.len() - 1 },
+        tail_ptr:      if input.len().is_multiple_of(2) { input.len() - 2 } else { input.len() - 1 },
         tail_consumed: BlockCount(0),
     };
 
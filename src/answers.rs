@@ -0,0 +1,70 @@
+//! Expected answers for each day and part.
+//!
+//! Answers are stored next to the inputs in `input/d{day}.{mode}.answers.txt`,
+//! one `{part}: {answer}` entry per line.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::{input_dir, Mode};
+
+pub fn path(mode: Mode, day: u32) -> anyhow::Result<PathBuf> {
+    Ok(input_dir()?.join(format!("d{day}.{}.answers.txt", mode.name())))
+}
+
+/// Loads all recorded answers of a day as `(part, answer)` pairs.
+fn load_all(mode: Mode, day: u32) -> anyhow::Result<Vec<(u32, String)>> {
+    let path = path(mode, day)?;
+    if !fs::exists(&path).with_context(|| format!("test {} existence", path.display()))? {
+        return Ok(Vec::new());
+    }
+    read(&path)
+}
+
+/// Reads a file of `{part}: {answer}` lines as `(part, answer)` pairs.
+pub fn read(path: &Path) -> anyhow::Result<Vec<(u32, String)>> {
+    let file = fs::read_to_string(path).with_context(|| format!("read file {}", path.display()))?;
+    let mut answers = Vec::new();
+    for (line_no, line) in file.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (part, answer) = line.split_once(':').with_context(|| {
+            format!("{}:{}: expected `part: answer`", path.display(), line_no + 1)
+        })?;
+        let part: u32 = part
+            .trim()
+            .parse()
+            .with_context(|| format!("{}:{}: invalid part number", path.display(), line_no + 1))?;
+        answers.push((part, answer.trim().to_string()));
+    }
+
+    Ok(answers)
+}
+
+/// Loads the expected answer of a part, or `None` if it has not been recorded.
+pub fn load(mode: Mode, day: u32, part: u32) -> anyhow::Result<Option<String>> {
+    let answers = load_all(mode, day)?;
+    Ok(answers.into_iter().find(|&(line_part, _)| line_part == part).map(|(_, answer)| answer))
+}
+
+/// Records the expected answer of a part, replacing any previously recorded answer.
+pub fn record(mode: Mode, day: u32, part: u32, answer: &str) -> anyhow::Result<()> {
+    let mut answers = load_all(mode, day)?;
+    answers.retain(|&(line_part, _)| line_part != part);
+    answers.push((part, answer.trim().to_string()));
+    answers.sort_by_key(|&(line_part, _)| line_part);
+
+    let path = path(mode, day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("create input directory {}", dir.display()))?;
+    }
+
+    let file: String =
+        answers.iter().map(|(line_part, answer)| format!("{line_part}: {answer}\n")).collect();
+    fs::write(&path, file).with_context(|| format!("write file {}", path.display()))
+}
@@ -0,0 +1,244 @@
+//! Client for the AoC website, shared by all commands that access it.
+//!
+//! Requests identify this tool and the configured contact in the User-Agent,
+//! are spaced at least `request_interval` apart within the process,
+//! and are refused for days that are not unlocked yet.
+
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{env, fmt, io, thread};
+
+use anyhow::Context;
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::StatusCode;
+
+/// The year whose inputs and submissions are stored in the default input directory.
+pub const DEFAULT_YEAR: u32 = 2024;
+
+#[derive(Clone)]
+pub struct Config {
+    pub base_url:         String,
+    pub year:             u32,
+    /// Contact information included in the User-Agent, e.g. an email address or repository URL.
+    ///
+    /// Defaults to `$AOC_CONTACT`.
+    pub contact:          Option<String>,
+    pub request_interval: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url:         "https://adventofcode.com".to_string(),
+            year:             DEFAULT_YEAR,
+            contact:          None,
+            request_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Configures the client for the rest of the process.
+///
+/// Has no effect if the client has already been configured or used.
+pub fn configure(config: Config) { _ = CONFIG.set(config); }
+
+pub fn client() -> anyhow::Result<&'static Client> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = Client::new(CONFIG.get_or_init(Config::default).clone())?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+pub struct Client {
+    http:         reqwest::blocking::Client,
+    config:       Config,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    fn new(config: Config) -> anyhow::Result<Self> {
+        let mut user_agent = concat!("aoc2024/", env!("CARGO_PKG_VERSION")).to_string();
+        if let Some(contact) = config.contact.clone().or_else(|| env::var("AOC_CONTACT").ok()) {
+            user_agent = format!("{user_agent} ({contact})");
+        }
+
+        let http = reqwest::blocking::Client::builder()
+            .user_agent(user_agent)
+            .build()
+            .context("build http client")?;
+        Ok(Self { http, config, last_request: Mutex::new(None) })
+    }
+
+    pub fn year(&self) -> u32 { self.config.year }
+
+    /// The days that have puzzles in the configured year.
+    pub fn days(&self) -> RangeInclusive<u32> {
+        if self.config.year >= 2025 {
+            1..=12
+        } else {
+            1..=25
+        }
+    }
+
+    /// Returns the time left until the puzzle of a day unlocks, or `None` if it is unlocked.
+    pub fn time_until_unlock(&self, day: u32) -> Option<Duration> {
+        unlock_time(self.config.year, day).duration_since(SystemTime::now()).ok()
+    }
+
+    fn ensure_unlocked(&self, day: u32) -> anyhow::Result<()> {
+        if !self.days().contains(&day) {
+            anyhow::bail!("{} has no puzzle for day {day}", self.config.year);
+        }
+        if let Some(remaining) = self.time_until_unlock(day) {
+            anyhow::bail!("day {day} of {} unlocks in {}", self.config.year, Countdown(remaining));
+        }
+        Ok(())
+    }
+
+    fn url(&self, day: u32, path: &str) -> String {
+        let base_url = self.config.base_url.trim_end_matches('/');
+        format!("{base_url}/{}/day/{day}{path}", self.config.year)
+    }
+
+    /// Sends a request after waiting for the request interval since the previous request.
+    fn send(
+        &self,
+        mut request: RequestBuilder,
+        session: Option<&str>,
+    ) -> reqwest::Result<Response> {
+        if let Some(session) = session {
+            request = request.header("Cookie", format!("session={session}"));
+        }
+
+        let mut last_request = self.last_request.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(last_request) = *last_request {
+            thread::sleep(self.config.request_interval.saturating_sub(last_request.elapsed()));
+        }
+        *last_request = Some(Instant::now());
+        request.send()
+    }
+
+    /// Downloads the private input of a day,
+    /// failing on anything other than a successful input response.
+    pub fn input(&self, day: u32) -> anyhow::Result<String> {
+        self.ensure_unlocked(day)?;
+        let session = session()?;
+
+        let response = self
+            .send(self.http.get(self.url(day, "/input")), Some(&session))
+            .context("request aoc private input")?;
+        let status = response.status();
+        let input = io::read_to_string(response).context("read aoc private input")?;
+
+        if input.contains("Please log in") {
+            return Err(SessionRejected.into());
+        }
+        if input.contains("Please don't repeatedly request") {
+            anyhow::bail!("day {day} input is not unlocked yet");
+        }
+        if status == StatusCode::NOT_FOUND {
+            anyhow::bail!("aoc has no input for day {day} (HTTP {status})");
+        }
+        if !status.is_success() {
+            let first_line = input.lines().next().unwrap_or_default();
+            anyhow::bail!("aoc private input request failed with HTTP {status}: {first_line}");
+        }
+        if input.trim_start().starts_with('<') {
+            anyhow::bail!("aoc returned an HTML page instead of the day {day} input");
+        }
+        if input.trim().is_empty() {
+            anyhow::bail!("aoc returned an empty input for day {day}");
+        }
+        Ok(input)
+    }
+
+    /// Downloads the puzzle page of a day, including part 2 if logged in with `AOC_SESSION`.
+    pub fn puzzle_page(&self, day: u32) -> anyhow::Result<String> {
+        self.ensure_unlocked(day)?;
+        let session = env::var("AOC_SESSION").ok();
+
+        let response = self
+            .send(self.http.get(self.url(day, "")), session.as_deref())
+            .context("request aoc puzzle page")?
+            .error_for_status()
+            .context("aoc puzzle page")?;
+        io::read_to_string(response).context("read aoc puzzle page")
+    }
+
+    /// Submits an answer and returns the response page.
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> anyhow::Result<String> {
+        self.ensure_unlocked(day)?;
+        let session = session()?;
+
+        let request = self
+            .http
+            .post(self.url(day, "/answer"))
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())]);
+        let response = self
+            .send(request, Some(&session))
+            .context("request aoc answer submission")?
+            .error_for_status()
+            .context("aoc answer submission")?;
+        io::read_to_string(response).context("read aoc answer response")
+    }
+}
+
+/// Reads the session cookie that authenticates requests for private data.
+pub fn session() -> anyhow::Result<String> {
+    env::var("AOC_SESSION").context("AOC_SESSION env var missing")
+}
+
+/// The error of a request that AoC answered with a login page,
+/// which fails the same way for every other day until the session is replaced.
+#[derive(Debug)]
+pub struct SessionRejected;
+
+impl fmt::Display for SessionRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "aoc rejected the session cookie, AOC_SESSION is invalid or expired")
+    }
+}
+
+impl std::error::Error for SessionRejected {}
+
+/// Returns the time a puzzle unlocks, midnight EST (UTC-5) on December `day`.
+fn unlock_time(year: u32, day: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(december_days_since_epoch(year, day) * 86_400 + 5 * 3_600)
+}
+
+/// Days from 1970-01-01 to December `day` of `year`, following Howard Hinnant's
+/// `days_from_civil` with years starting on March 1.
+fn december_days_since_epoch(year: u32, day: u32) -> u64 {
+    let year = u64::from(year);
+    let (era, year_of_era) = (year / 400, year % 400);
+    // 275 days from March 1 to December 1.
+    let day_of_year = 275 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Formats a duration as e.g. `2d 3h 4m 5s`, omitting leading zero units.
+pub struct Countdown(pub Duration);
+
+impl fmt::Display for Countdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Round up so that the countdown never reads 0s before the unlock.
+        let secs = self.0.as_secs() + u64::from(self.0.subsec_nanos() > 0);
+        let units = [(secs / 86_400, "d"), (secs / 3_600 % 24, "h"), (secs / 60 % 60, "m")];
+
+        let mut started = false;
+        for (value, unit) in units {
+            started |= value > 0;
+            if started {
+                write!(f, "{value}{unit} ")?;
+            }
+        }
+        write!(f, "{}s", secs % 60)
+    }
+}
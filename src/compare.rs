@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use crate::all::{self, TimedOut, SOLUTIONS};
+use crate::{CompareArgs, InputSource, Repetition};
+
+pub fn run(args: CompareArgs) -> anyhow::Result<()> {
+    let source = InputSource::new(args.mode, args.input);
+    let timeout = Duration::from_secs(args.timeout);
+    let mut disagreements = 0;
+    let mut load_failures = 0;
+    let mut timeouts = 0;
+
+    for (day, day_variants) in &SOLUTIONS
+        .iter()
+        .filter(|variant| args.day.is_none_or(|day| variant.day == day))
+        .chunk_by(|variant| variant.day)
+    {
+        let input: Arc<str> = match source.load(day) {
+            Ok(input) => input.into(),
+            Err(err) => {
+                load_failures += 1;
+                println!("Day {day}: {err:#}");
+                continue;
+            }
+        };
+
+        for (part, variants) in &day_variants.chunk_by(|variant| variant.part) {
+            let outputs: Vec<_> = variants
+                .map(|variant| {
+                    let output =
+                        match variant.solve_isolated(input.clone(), Repetition::ONCE, timeout) {
+                            Ok(outcome) => outcome.output.trim().to_string(),
+                            Err(err) => {
+                                timeouts += usize::from(err.is::<TimedOut>());
+                                format!("error: {err:#}")
+                            }
+                        };
+                    (variant.name, output)
+                })
+                .collect();
+
+            if let [(name, output)] = &outputs[..] {
+                println!("Day {day} part {part}: only variant {name} outputs {output}");
+            } else if outputs.iter().map(|(_, output)| output).all_equal() {
+                println!(
+                    "Day {day} part {part}: {} variants agree on {}",
+                    outputs.len(),
+                    outputs[0].1
+                );
+            } else {
+                disagreements += 1;
+                println!("Day {day} part {part}: variants disagree");
+                let name_width = outputs.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+                for (name, output) in &outputs {
+                    println!("  {name:name_width$}  {output}");
+                }
+            }
+        }
+    }
+
+    all::warn_timeouts(timeouts);
+    match (disagreements, load_failures) {
+        (0, 0) => {}
+        (_, 0) => anyhow::bail!("variants disagree in {disagreements} parts"),
+        (0, _) => anyhow::bail!("{load_failures} inputs failed to load"),
+        _ => anyhow::bail!(
+            "variants disagree in {disagreements} parts and {load_failures} inputs failed to load"
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! Downloading of private inputs ahead of running solutions.
+
+use std::fs;
+
+use anyhow::Context;
+
+use crate::{aoc, download_input, input_path, FetchArgs, Mode};
+
+pub fn run(args: FetchArgs) -> anyhow::Result<()> {
+    let client = aoc::client()?;
+    let days: Vec<u32> = match args.day {
+        Some(day) => vec![day],
+        None => client.days().take_while(|&day| client.time_until_unlock(day).is_none()).collect(),
+    };
+    if days.is_empty() {
+        let first_day = *client.days().start();
+        let remaining = client.time_until_unlock(first_day).unwrap_or_default();
+        anyhow::bail!(
+            "no day of {} is unlocked yet, day {first_day} unlocks in {}",
+            client.year(),
+            aoc::Countdown(remaining),
+        );
+    }
+
+    // Fail once up front rather than once per day if no session is configured.
+    aoc::session()?;
+
+    let mut failures = 0;
+    for day in days {
+        let path = input_path(Mode::Private, day)?;
+        if !args.force
+            && fs::exists(&path).with_context(|| format!("test {} existence", path.display()))?
+        {
+            println!("Day {day}: already downloaded to {}", path.display());
+            continue;
+        }
+
+        match download_input(day, &path) {
+            Ok(()) => println!("Day {day}: downloaded to {}", path.display()),
+            Err(err) if err.is::<aoc::SessionRejected>() => {
+                return Err(err.context(format!("download day {day} input")));
+            }
+            Err(err) => {
+                failures += 1;
+                println!("Day {day}: {err:#}");
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{failures} downloads failed");
+    }
+    Ok(())
+}
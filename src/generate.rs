@@ -0,0 +1,304 @@
+//! Synthetic puzzle inputs with the same structure as the real ones.
+//!
+//! Each generator takes a seed and an approximate input size in bytes,
+//! so that inputs are reproducible and can be scaled for benchmarks.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io::{self, Write as _};
+
+use anyhow::Context;
+
+use crate::GenerateArgs;
+
+/// A SplitMix64 generator, so that generated inputs stay stable across dependency upgrades.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self { Self(seed) }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniform integer in `low..=high`.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 { low + self.next_u64() % (high - low + 1) }
+
+    /// Returns true with a probability of `percent`%.
+    pub fn chance(&mut self, percent: u64) -> bool { self.range(1, 100) <= percent }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i as u64) as usize);
+        }
+    }
+}
+
+/// An input generator for a day.
+pub struct Generator {
+    pub day:          u32,
+    /// The approximate size of the real inputs in bytes.
+    pub default_size: usize,
+    generate:         fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator { day: 1, default_size: 14_000, generate: d1 },
+    Generator { day: 2, default_size: 19_000, generate: d2 },
+    Generator { day: 3, default_size: 18_000, generate: d3 },
+    Generator { day: 4, default_size: 19_740, generate: d4 },
+    Generator { day: 5, default_size: 15_000, generate: d5 },
+    Generator { day: 6, default_size: 17_030, generate: d6 },
+    Generator { day: 7, default_size: 25_000, generate: d7 },
+    Generator { day: 8, default_size: 2_550, generate: d8 },
+    Generator { day: 9, default_size: 20_000, generate: d9 },
+];
+
+pub fn find(day: u32) -> anyhow::Result<&'static Generator> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+        .with_context(|| format!("No input generator for day {day}"))
+}
+
+/// Location ID lists, one `left   right` pair per line.
+fn d1(rng: &mut Rng, size: usize) -> String {
+    let lines = (size / 14).max(1);
+    let left: Vec<u64> = (0..lines).map(|_| rng.range(10_000, 99_999)).collect();
+
+    let mut output = String::new();
+    for &l in &left {
+        // Reuse left IDs on the right so that similarity scores are non-trivial.
+        let r = if rng.chance(30) { *rng.pick(&left) } else { rng.range(10_000, 99_999) };
+        writeln!(output, "{l}   {r}").unwrap();
+    }
+    output
+}
+
+/// Reports of 5 to 8 levels, mostly gradual with occasional bad steps.
+fn d2(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    while output.len() < size.max(1) {
+        let len = rng.range(5, 8);
+        let increasing = rng.chance(50);
+        let mut level = rng.range(20, 80);
+        let mut levels = vec![level];
+        for _ in 1..len {
+            let step = if rng.chance(5) { rng.range(0, 6) } else { rng.range(1, 3) };
+            let up = increasing != rng.chance(5);
+            level = if up { level + step } else { level.saturating_sub(step).max(1) };
+            levels.push(level);
+        }
+
+        let line: Vec<String> = levels.iter().map(u64::to_string).collect();
+        writeln!(output, "{}", line.join(" ")).unwrap();
+    }
+    output
+}
+
+/// Corrupted memory with `mul(a,b)`, `do()` and `don't()` instructions among noise.
+fn d3(rng: &mut Rng, size: usize) -> String {
+    const NOISE: &[u8] =
+        b"!@#$%^&*()[]{}<>,;:'?/+-_=~ select where from who how what when why mul do";
+
+    let mut output = String::new();
+    while output.len() < size.max(1) {
+        match rng.range(0, 19) {
+            0..=7 => write!(output, "mul({},{})", rng.range(1, 999), rng.range(1, 999)).unwrap(),
+            8 => output.push_str("do()"),
+            9 => output.push_str("don't()"),
+            10 => write!(output, "mul({} ,{})", rng.range(1, 999), rng.range(1, 999)).unwrap(),
+            11 => write!(output, "mul({}*", rng.range(1, 999)).unwrap(),
+            12 if rng.chance(10) => output.push('\n'),
+            _ => {
+                for _ in 0..rng.range(1, 6) {
+                    output.push(char::from(*rng.pick(NOISE)));
+                }
+            }
+        }
+    }
+    output.push('\n');
+    output
+}
+
+/// Returns the side length of a square grid of about `size` bytes including newlines.
+fn grid_side(size: usize) -> usize { (size as f64).sqrt().max(2.0) as usize }
+
+/// A square word search grid of the letters of `XMAS`.
+fn d4(rng: &mut Rng, size: usize) -> String {
+    let side = grid_side(size);
+
+    let mut output = String::new();
+    for _ in 0..side {
+        for _ in 0..side {
+            output.push(char::from(*rng.pick(b"XMAS")));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Ordering rules over a total order of pages, followed by updates of odd length.
+fn d5(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<u64> = (11..=99).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut rules = Vec::new();
+    for (i, &earlier) in pages.iter().enumerate() {
+        for &later in &pages[i + 1..] {
+            rules.push((earlier, later));
+        }
+    }
+    rng.shuffle(&mut rules);
+    let mut output: String =
+        rules.iter().map(|(earlier, later)| format!("{earlier}|{later}\n")).collect();
+    output.push('\n');
+
+    let updates_size = size.saturating_sub(output.len()).max(1);
+    let updates_start = output.len();
+    while output.len() - updates_start < updates_size {
+        let len = rng.range(2, 11) * 2 + 1;
+        let mut indices: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut indices);
+        indices.truncate(len as usize);
+        if rng.chance(50) {
+            indices.sort_unstable();
+        }
+
+        let update: Vec<String> = indices.iter().map(|&index| pages[index].to_string()).collect();
+        writeln!(output, "{}", update.join(",")).unwrap();
+    }
+    output
+}
+
+/// Returns the number of cells patrolled by a guard starting at `start` facing up,
+/// or `None` if the guard never leaves the grid.
+fn d6_patrol(grid: &[Vec<u8>], start: (usize, usize)) -> Option<usize> {
+    let side = grid.len() as isize;
+    let (mut x, mut y) = (start.0 as isize, start.1 as isize);
+    let (mut dx, mut dy) = (0, -1);
+    let mut visited = HashSet::new();
+    while visited.insert((x, y, dx, dy)) {
+        let (nx, ny) = (x + dx, y + dy);
+        if !(0..side).contains(&nx) || !(0..side).contains(&ny) {
+            let cells: HashSet<_> = visited.into_iter().map(|(x, y, _, _)| (x, y)).collect();
+            return Some(cells.len());
+        }
+        if grid[ny as usize][nx as usize] == b'#' {
+            (dx, dy) = (-dy, dx);
+        } else {
+            (x, y) = (nx, ny);
+        }
+    }
+    None
+}
+
+/// A square lab map with sparse obstructions and a guard whose patrol leaves the map.
+///
+/// The guard is placed at the candidate start with the longest patrol,
+/// since random starts mostly leave the map within a few steps, unlike real inputs.
+fn d6(rng: &mut Rng, size: usize) -> String {
+    let side = grid_side(size);
+
+    loop {
+        let mut grid: Vec<Vec<u8>> = (0..side)
+            .map(|_| (0..side).map(|_| if rng.chance(5) { b'#' } else { b'.' }).collect())
+            .collect();
+
+        let start = (0..100)
+            .map(|_| {
+                (rng.range(0, side as u64 - 1) as usize, rng.range(0, side as u64 - 1) as usize)
+            })
+            .filter(|&(x, y)| grid[y][x] == b'.')
+            .filter_map(|start| Some((d6_patrol(&grid, start)?, start)))
+            .max();
+        let Some((_, start)) = start else { continue };
+        grid[start.1][start.0] = b'^';
+
+        return grid
+            .into_iter()
+            .map(|row| String::from_utf8(row).expect("grid is ascii") + "\n")
+            .collect();
+    }
+}
+
+/// Calibration equations, about half of which are solvable with `+`, `*` and `||`.
+///
+/// Results are kept below 10^12 so that totals remain exact in jq.
+fn d7(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    while output.len() < size.max(1) {
+        let operands: Vec<u64> = (0..rng.range(2, 8)).map(|_| rng.range(1, 999)).collect();
+
+        let result =
+            operands[1..].iter().try_fold(operands[0], |acc, &operand| match rng.range(0, 2) {
+                0 => acc.checked_add(operand),
+                1 => acc.checked_mul(operand),
+                _ => acc.checked_mul(10u64.pow(operand.ilog10() + 1))?.checked_add(operand),
+            });
+        let Some(mut result) = result.filter(|&result| result < 1_000_000_000_000) else {
+            continue;
+        };
+        if rng.chance(50) {
+            result += rng.range(1, 9);
+        }
+
+        let operands: Vec<String> = operands.iter().map(u64::to_string).collect();
+        writeln!(output, "{result}: {}", operands.join(" ")).unwrap();
+    }
+    output
+}
+
+/// A square map of antennas, with a few antennas per frequency.
+fn d8(rng: &mut Rng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let side = grid_side(size);
+    let mut grid = vec![vec![b'.'; side]; side];
+
+    let frequencies = (side * side / 60).clamp(1, FREQUENCIES.len());
+    for &frequency in &FREQUENCIES[..frequencies] {
+        for _ in 0..rng.range(2, 4) {
+            let x = rng.range(0, side as u64 - 1) as usize;
+            let y = rng.range(0, side as u64 - 1) as usize;
+            grid[y][x] = frequency;
+        }
+    }
+
+    grid.into_iter().map(|row| String::from_utf8(row).expect("grid is ascii") + "\n").collect()
+}
+
+/// A disk map alternating file lengths (1 to 9) and free space lengths (0 to 9).
+fn d9(rng: &mut Rng, size: usize) -> String {
+    let digits = size.max(1) | 1;
+
+    let mut output: String = (0..digits)
+        .map(|index| {
+            let digit = if index % 2 == 0 { rng.range(1, 9) } else { rng.range(0, 9) };
+            char::from(b'0' + digit as u8)
+        })
+        .collect();
+    output.push('\n');
+    output
+}
+
+pub fn run(args: GenerateArgs) -> anyhow::Result<()> {
+    let generator = find(args.day)?;
+    let input = generator.generate(args.seed, args.size.unwrap_or(generator.default_size));
+    io::stdout().write_all(input.as_bytes()).context("write generated input")
+}
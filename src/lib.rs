@@ -1,11 +1,25 @@
-use std::path::PathBuf;
-use std::{env, fs, io};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fmt, fs, io, process};
 
 use anyhow::Context;
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use report::OutputFormat;
 
 pub mod all;
-pub use all::run;
+pub mod answers;
+pub mod aoc;
+mod compare;
+mod fetch;
+pub mod generate;
+mod list;
+mod puzzle;
+mod report;
+mod stats;
+mod submit;
+mod table;
+mod verify;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Mode {
@@ -13,63 +27,408 @@ pub enum Mode {
     Private,
 }
 
+impl Mode {
+    pub const ALL: &[Self] = &[Self::Sample, Self::Private];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Sample => "sample",
+            Mode::Private => "private",
+        }
+    }
+}
+
 #[derive(Parser)]
 pub struct Args {
+    /// Directory to read inputs and answers from and to cache downloaded inputs in.
+    ///
+    /// Defaults to `$AOC2024_INPUT_DIR`, then `$CARGO_MANIFEST_DIR/input`, which is the `input`
+    /// directory of the crate under `cargo run`, then `$XDG_CACHE_HOME/aoc2024` or
+    /// `$HOME/.cache/aoc2024`.
+    #[clap(long, global = true)]
+    input_dir: Option<PathBuf>,
+
+    /// Base URL of the AoC website.
+    #[clap(long, global = true, default_value = "https://adventofcode.com")]
+    base_url: String,
+
+    /// The AoC event to download inputs and puzzles of and to submit answers to.
+    ///
+    /// Inputs and submissions are stored by day only,
+    /// so years other than the default require `--input-dir`.
+    #[clap(long, global = true, default_value_t = aoc::DEFAULT_YEAR)]
+    #[clap(value_parser = clap::value_parser!(u32).range(2015..))]
+    year: u32,
+
+    /// Contact information to include in the User-Agent of requests to the AoC website,
+    /// such as an email address or repository URL. Defaults to `$AOC_CONTACT`.
+    #[clap(long, global = true)]
+    contact: Option<String>,
+
+    /// Minimum number of seconds between requests to the AoC website.
+    #[clap(long, global = true, default_value_t = 5)]
+    request_interval: u64,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution variants that match the optional day, part and variant filters.
+    ///
+    /// Runs every variant if no filter is given.
+    /// With the text output format, a variant selected by all three filters prints its output
+    /// with its parse and execution times.
+    /// Otherwise the variants are run with a timeout and reported together.
+    Run(RunArgs),
+    /// Run every solution variant and compare with the recorded answers.
+    Verify(VerifyArgs),
+    /// Check that all variants of each part produce the same output.
+    Compare(CompareArgs),
+    /// Run a solution on the private input and submit its answer.
+    Submit(SubmitArgs),
+    /// Download the private input of a day, or of every unlocked day.
+    Fetch(FetchArgs),
+    /// Download the puzzle page of a day and extract its sample input and answers.
+    FetchPuzzle(FetchPuzzleArgs),
+    /// List all registered solutions and the locally available inputs and answers.
+    List,
+    /// Print a synthetic input for a day to stdout.
+    Generate(GenerateArgs),
+}
+
+#[derive(clap::Args)]
+pub struct RunArgs {
     mode:    Mode,
+    /// Only run solutions of this day. Runs all days if unspecified.
+    day:     Option<u32>,
+    /// Only run solutions of this part. Runs all parts if unspecified.
+    part:    Option<u32>,
+    /// Only run this variant. Runs all variants if unspecified.
+    variant: Option<String>,
+
+    /// Read the input from this file, or `-` for stdin, instead of the stored input of `mode`.
+    #[clap(long, requires = "day")]
+    input: Option<PathBuf>,
+
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    #[clap(flatten)]
+    repetition: Repetition,
+
+    /// Seconds after which a variant is reported as timed out when running several variants.
+    #[clap(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+}
+
+#[derive(Clone, Copy, clap::Args)]
+pub struct Repetition {
+    /// Number of timed runs of each solution.
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub repeat: u32,
+    /// Number of untimed runs before the timed runs.
+    #[clap(long, default_value_t = 0)]
+    pub warmup: u32,
+}
+
+impl Repetition {
+    pub const ONCE: Self = Self { repeat: 1, warmup: 0 };
+}
+
+#[derive(clap::Args)]
+pub struct VerifyArgs {
+    /// Only verify inputs of this mode. Verifies all modes if unspecified.
+    #[clap(long)]
+    mode:    Option<Mode>,
+    /// Seconds after which a variant is reported as timed out.
+    #[clap(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+}
+
+#[derive(clap::Args)]
+pub struct CompareArgs {
+    mode:    Mode,
+    /// Only compare variants of this day.
+    #[clap(long)]
+    day:     Option<u32>,
+    /// Read the input from this file, or `-` for stdin, instead of the stored input of `mode`.
+    #[clap(long, requires = "day")]
+    input:   Option<PathBuf>,
+    /// Seconds after which a variant is reported as timed out.
+    #[clap(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: u64,
+}
+
+#[derive(clap::Args)]
+pub struct SubmitArgs {
     day:     u32,
     part:    u32,
-    #[clap(default_value = "")]
-    variant: String,
+    /// The variant to compute the answer with. Uses the first variant if unspecified.
+    variant: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct FetchArgs {
+    /// The day to download the input of.
+    #[clap(required_unless_present = "all", conflicts_with = "all")]
+    day:   Option<u32>,
+    /// Download the inputs of all unlocked days.
+    #[clap(long)]
+    all:   bool,
+    /// Download inputs again even if they are already cached.
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(clap::Args)]
+pub struct FetchPuzzleArgs {
+    day:   u32,
+    /// The 1-based index of the example block to use as the sample input.
+    #[clap(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    block: u64,
+    /// Overwrite existing sample inputs and answers.
+    #[clap(long)]
+    force: bool,
+}
+
+#[derive(clap::Args)]
+pub struct GenerateArgs {
+    day:  u32,
+    /// Seed of the generator. The same seed and size always generate the same input.
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+    /// Approximate size of the input in bytes. Defaults to the size of real inputs.
+    #[clap(long)]
+    size: Option<usize>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    if args.year != aoc::DEFAULT_YEAR && args.input_dir.is_none() {
+        anyhow::bail!(
+            "--year {} requires --input-dir, since the default one holds the inputs and \
+             submissions of {}",
+            args.year,
+            aoc::DEFAULT_YEAR,
+        );
+    }
+    if let Some(dir) = args.input_dir {
+        set_input_dir(dir);
+    }
+    aoc::configure(aoc::Config {
+        base_url:         args.base_url,
+        year:             args.year,
+        contact:          args.contact,
+        request_interval: Duration::from_secs(args.request_interval),
+    });
+
+    match args.command {
+        Command::Run(args) => all::run(args),
+        Command::Verify(args) => verify::run(args),
+        Command::Compare(args) => compare::run(args),
+        Command::Submit(args) => submit::run(args),
+        Command::Fetch(args) => fetch::run(args),
+        Command::FetchPuzzle(args) => puzzle::run(args),
+        Command::List => list::run(),
+        Command::Generate(args) => generate::run(args),
+    }
+}
+
+static INPUT_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Overrides the input directory for the rest of the process.
+///
+/// Has no effect if the input directory has already been overridden.
+pub fn set_input_dir(dir: PathBuf) { _ = INPUT_DIR_OVERRIDE.set(dir); }
+
+/// Returns the directory that inputs and answers are read from and downloads are cached in.
+///
+/// This is the directory set with [`set_input_dir`], then `$AOC2024_INPUT_DIR`,
+/// then `$CARGO_MANIFEST_DIR/input`, so that `cargo run`, tests and benchmarks use the inputs
+/// checked out with the crate, then `$XDG_CACHE_HOME/aoc2024` or `$HOME/.cache/aoc2024`.
+pub fn input_dir() -> anyhow::Result<PathBuf> {
+    if let Some(dir) = INPUT_DIR_OVERRIDE.get() {
+        return Ok(dir.clone());
+    }
+
+    if let Some(dir) = env::var_os("AOC2024_INPUT_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(dir) = env::var_os("CARGO_MANIFEST_DIR") {
+        return Ok(PathBuf::from(dir).join("input"));
+    }
+
+    let cache_dir = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .context("cannot locate cache directory, specify --input-dir or AOC2024_INPUT_DIR")?;
+    Ok(cache_dir.join("aoc2024"))
+}
+
+pub fn input_path(mode: Mode, day: u32) -> anyhow::Result<PathBuf> {
+    Ok(input_dir()?.join(format!("d{day}.{}.input.txt", mode.name())))
 }
 
 pub fn load_input(mode: Mode, day: u32) -> anyhow::Result<String> {
-    let dir = env::var("CARGO_MANIFEST_DIR").context("need cargo run")?;
-    let path = PathBuf::from(dir).join("input").join(format!(
-        "d{day}.{}.input.txt",
-        match mode {
-            Mode::Sample => "sample",
-            Mode::Private => "private",
-        }
-    ));
+    let path = input_path(mode, day)?;
 
     if let Mode::Private = mode {
         let exists =
             fs::exists(&path).with_context(|| format!("test {} existence", path.display()))?;
         if !exists {
             eprintln!("Downloading day {day} input");
-
-            let session_cookie = env::var("AOC_SESSION")
-                .context("private file missing and AOC_SESSION env var missing")?;
-
-            let client = reqwest::blocking::Client::new();
-            let data = client
-                .get(format!("https://adventofcode.com/2024/day/{day}/input"))
-                .header("Cookie", format!("session={session_cookie}"))
-                .send()
-                .context("request aoc private input")?;
-            let input = io::read_to_string(data).context("read aoc private input")?;
-            fs::write(&path, &input).context("write aoc private input to cache")?;
+            download_input(day, &path)?;
         }
     }
 
     fs::read_to_string(&path).with_context(|| format!("read file {}", path.display()))
 }
 
+/// Downloads the private input of a day to `path`.
+fn download_input(day: u32, path: &Path) -> anyhow::Result<()> {
+    let input = aoc::client()?.input(day)?;
+    write_atomic(path, &input).context("write aoc private input to cache")
+}
+
+/// Writes a file through a temporary file in the same directory,
+/// so that an interrupted write never leaves a truncated file at `path`.
+fn write_atomic(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("create input directory {}", dir.display()))?;
+    }
+
+    let mut temp_name = path.file_name().context("path has no file name")?.to_os_string();
+    temp_name.push(format!(".{}.tmp", process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = fs::write(&temp_path, contents)
+        .with_context(|| format!("write file {}", temp_path.display()))
+        .and_then(|()| {
+            fs::rename(&temp_path, path)
+                .with_context(|| format!("rename {} to {}", temp_path.display(), path.display()))
+        });
+    if result.is_err() {
+        _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Where the puzzle input of a run is read from.
+pub enum InputSource {
+    /// The sample or private input in the input directory.
+    Stored(Mode),
+    /// An arbitrary file, used for all days.
+    File(PathBuf),
+    /// The standard input, used for all days.
+    Stdin,
+}
+
+impl InputSource {
+    pub fn new(mode: Mode, path: Option<PathBuf>) -> Self {
+        match path {
+            None => Self::Stored(mode),
+            Some(path) if path.as_os_str() == "-" => Self::Stdin,
+            Some(path) => Self::File(path),
+        }
+    }
+
+    pub fn load(&self, day: u32) -> anyhow::Result<String> {
+        match self {
+            Self::Stored(mode) => load_input(*mode, day),
+            Self::File(path) => {
+                fs::read_to_string(path).with_context(|| format!("read file {}", path.display()))
+            }
+            Self::Stdin => io::read_to_string(io::stdin()).context("read input from stdin"),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stored(mode) => write!(f, "{}", mode.name()),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
 pub trait Parse: Clone {
-    fn parse(input: &str) -> Self;
+    fn parse(input: &str) -> Result<Self, ParseError>;
+}
+
+/// An error in the puzzle input, located by line and column.
+#[derive(Debug)]
+pub struct ParseError {
+    /// 1-based line number of the offending input.
+    pub line:    usize,
+    /// 1-based column number of the offending input, in characters.
+    pub column:  usize,
+    /// The input starting from the offending position, up to the end of the line.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    const MAX_SNIPPET_CHARS: usize = 32;
+
+    /// Creates an error located at the start of `at`, which must be a subslice of `input`.
+    pub fn at(input: &str, at: &str, message: impl fmt::Display) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .expect("`at` must be a subslice of `input`");
+
+        let (before, after) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line_rest = after.split('\n').next().unwrap_or_default();
+
+        let mut snippet: String = line_rest.chars().take(Self::MAX_SNIPPET_CHARS).collect();
+        if snippet.len() < line_rest.len() {
+            snippet.push_str("...");
+        }
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet,
+            message: message.to_string(),
+        }
+    }
+
+    /// Parses `token`, a subslice of `input`, with its [`FromStr`](std::str::FromStr) impl.
+    pub fn parse_token<T>(input: &str, token: &str) -> Result<T, Self>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        token.parse().map_err(|err| Self::at(input, token, err))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {} (at {:?})", self.line, self.column, self.message, self.snippet)
+    }
 }
 
+impl std::error::Error for ParseError {}
+
 impl Parse for String {
-    fn parse(input: &str) -> Self { input.to_string() }
+    fn parse(input: &str) -> Result<Self, ParseError> { Ok(input.to_string()) }
 }
 
 #[derive(Clone)]
 pub struct JsonString(pub String);
 
 impl Parse for JsonString {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, ParseError> {
         let value = simd_json::json!(input);
-        Self(simd_json::to_string(&value).unwrap())
+        Ok(Self(simd_json::to_string(&value).expect("strings are always serializable")))
     }
 }
 
@@ -0,0 +1,49 @@
+use std::fs;
+
+use anyhow::Context;
+use itertools::Itertools;
+
+use crate::all::SOLUTIONS;
+use crate::{answers, input_path, Mode};
+
+/// Describes which modes have a local file, e.g. `sample input, no private input`.
+fn availability(
+    kind: &str,
+    mut exists: impl FnMut(Mode) -> anyhow::Result<bool>,
+) -> anyhow::Result<String> {
+    Ok(Mode::ALL
+        .iter()
+        .map(|&mode| {
+            Ok(if exists(mode)? {
+                format!("{} {kind}", mode.name())
+            } else {
+                format!("no {} {kind}", mode.name())
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .join(", "))
+}
+
+pub fn run() -> anyhow::Result<()> {
+    for (day, day_solutions) in &SOLUTIONS.iter().chunk_by(|soln| soln.day) {
+        let inputs = availability("input", |mode| {
+            let path = input_path(mode, day)?;
+            fs::exists(&path).with_context(|| format!("test {} existence", path.display()))
+        })?;
+        println!("Day {day}: {inputs}");
+
+        for (part, part_solutions) in &day_solutions.chunk_by(|soln| soln.part) {
+            let answers =
+                availability("answer", |mode| Ok(answers::load(mode, day, part)?.is_some()))?;
+            println!("  Part {part}: {answers}");
+
+            let part_solutions: Vec<_> = part_solutions.collect();
+            let name_width = part_solutions.iter().map(|soln| soln.name.len()).max().unwrap_or(0);
+            for soln in part_solutions {
+                println!("    {:name_width$}  {}", soln.name, soln.backend.name());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Extraction of sample inputs and answers from puzzle pages.
+
+use std::{fs, iter};
+
+use anyhow::Context;
+
+use crate::{answers, aoc, input_path, write_atomic, FetchPuzzleArgs, Mode};
+
+/// Iterates over the contents between each `open` and the following `close`.
+fn between<'a>(mut html: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    iter::from_fn(move || {
+        let start = html.find(open)? + open.len();
+        let len = html[start..].find(close)?;
+        let inner = &html[start..start + len];
+        html = &html[start + len + close.len()..];
+        Some(inner)
+    })
+}
+
+/// Converts an HTML fragment to plain text.
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = match rest[start..].find('>') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+    text.push_str(rest);
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Example blocks are the `<pre><code>` blocks of the puzzle descriptions.
+fn example_blocks(html: &str) -> Vec<String> {
+    between(html, "<pre><code>", "</code></pre>").map(html_to_text).collect()
+}
+
+/// Guesses the sample answer of each part from the last emphasized code in its description.
+fn guess_answers(html: &str) -> Vec<Option<String>> {
+    between(html, "<article class=\"day-desc\">", "</article>")
+        .map(|article| between(article, "<code><em>", "</em></code>").last().map(html_to_text))
+        .collect()
+}
+
+pub fn run(args: FetchPuzzleArgs) -> anyhow::Result<()> {
+    let day = args.day;
+
+    let html = aoc::client()?.puzzle_page(day)?;
+
+    let blocks = example_blocks(&html);
+    for (index, block) in blocks.iter().enumerate() {
+        println!("Example block {}: {} lines", index + 1, block.lines().count());
+    }
+
+    let block = blocks.get(args.block as usize - 1).with_context(|| {
+        format!("puzzle page has {} example blocks, cannot use block {}", blocks.len(), args.block)
+    })?;
+    let path = input_path(Mode::Sample, day)?;
+    if !args.force && fs::exists(&path).context("test sample input existence")? {
+        println!("Not overwriting {}", path.display());
+    } else {
+        write_atomic(&path, block)?;
+        println!("Wrote example block {} to {}", args.block, path.display());
+    }
+
+    for (part, answer) in (1..).zip(guess_answers(&html)) {
+        let Some(answer) = answer else {
+            println!("Part {part}: no answer found");
+            continue;
+        };
+
+        if !args.force && answers::load(Mode::Sample, day, part)?.is_some() {
+            println!("Part {part}: guessed {answer}, not overwriting recorded answer");
+        } else {
+            answers::record(Mode::Sample, day, part, &answer)?;
+            println!("Part {part}: guessed {answer}, please review");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A saved puzzle page with both parts, shortened.
+    const PAGE: &str = include_str!("../tests/fixtures/puzzle/d1.html");
+
+    #[test]
+    fn extract_example_blocks() {
+        assert_eq!(
+            example_blocks(PAGE),
+            ["3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", "a < b && c\n"]
+        );
+    }
+
+    #[test]
+    fn guess_sample_answers() {
+        assert_eq!(guess_answers(PAGE), [Some("11".to_string()), Some("31".to_string())]);
+    }
+
+    #[test]
+    fn guess_without_emphasized_code() {
+        let page = PAGE.replace("<code><em>31</em></code>", "<code>31</code>");
+        assert_eq!(guess_answers(&page), [Some("11".to_string()), None]);
+    }
+}
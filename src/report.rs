@@ -0,0 +1,182 @@
+//! Formatting of solution run results.
+
+use std::iter;
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use simd_json::prelude::MutableObject;
+
+use crate::all::{Backend, Outcome, SolutionDescriptor};
+use crate::stats::{Millis, Samples};
+use crate::table::Table;
+use crate::InputSource;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable table.
+    Text,
+    /// A JSON array with one object per record.
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+/// The result of running one solution variant.
+pub struct Record {
+    pub day:     u32,
+    pub part:    u32,
+    pub variant: &'static str,
+    pub backend: Backend,
+    /// The label of the input source.
+    pub mode:    String,
+    pub result:  anyhow::Result<Outcome>,
+}
+
+impl Record {
+    pub fn new(
+        source: &InputSource,
+        variant: &SolutionDescriptor,
+        result: anyhow::Result<Outcome>,
+    ) -> Self {
+        Self {
+            day: variant.day,
+            part: variant.part,
+            variant: variant.name,
+            backend: variant.backend,
+            mode: source.to_string(),
+            result,
+        }
+    }
+
+    fn parse_times(&self) -> Option<&Samples> {
+        self.result.as_ref().ok().map(|outcome| &outcome.parse_times)
+    }
+
+    fn exec_times(&self) -> Option<&Samples> {
+        self.result.as_ref().ok().map(|outcome| &outcome.exec_times)
+    }
+
+    fn answer(&self) -> Option<&str> {
+        self.result.as_ref().ok().map(|outcome| outcome.output.trim())
+    }
+
+    fn error(&self) -> Option<String> { self.result.as_ref().err().map(|err| format!("{err:#}")) }
+}
+
+pub fn print(format: OutputFormat, records: &[Record]) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Text => print_text(records),
+        OutputFormat::Json => print_json(records)?,
+        OutputFormat::Csv => print_csv(records),
+    }
+    Ok(())
+}
+
+/// Summary statistics of a phase, in the order of [`STAT_NAMES`].
+fn stats_ns(samples: Option<&Samples>) -> [Option<u64>; 5] {
+    let Some(samples) = samples else { return [None; 5] };
+    [samples.median(), samples.min(), samples.mean(), samples.p95(), samples.stddev()]
+        .map(|duration| Some(duration.as_nanos() as u64))
+}
+
+/// The median is reported without a suffix as the representative value.
+const STAT_NAMES: [&str; 5] = ["", "_min", "_mean", "_p95", "_stddev"];
+
+fn format_samples(samples: &Samples) -> String {
+    if samples.count() == 1 {
+        Millis(samples.median()).to_string()
+    } else {
+        format!("{} ±{}", Millis(samples.median()), Millis(samples.stddev()))
+    }
+}
+
+fn print_text(records: &[Record]) {
+    let mut table =
+        Table::new(["Day", "Part", "Variant", "Backend", "Parse time", "Execution time", "Output"]);
+
+    for record in records {
+        let (parse_time, exec_time, output) = match &record.result {
+            Ok(outcome) => (
+                format_samples(&outcome.parse_times),
+                format_samples(&outcome.exec_times),
+                outcome.output.trim().to_string(),
+            ),
+            Err(err) => (String::new(), String::new(), format!("error: {err:#}")),
+        };
+
+        table.push([
+            record.day.to_string(),
+            record.part.to_string(),
+            record.variant.to_string(),
+            record.backend.name().to_string(),
+            parse_time,
+            exec_time,
+            output,
+        ]);
+    }
+
+    table.print();
+}
+
+fn print_json(records: &[Record]) -> anyhow::Result<()> {
+    let values: Vec<_> = records
+        .iter()
+        .map(|record| {
+            let mut value = simd_json::json!({
+                "day": record.day,
+                "part": record.part,
+                "variant": record.variant,
+                "backend": record.backend.name(),
+                "mode": record.mode.as_str(),
+                "answer": record.answer(),
+                "error": record.error(),
+            });
+            for (phase, samples) in [("parse", record.parse_times()), ("exec", record.exec_times())]
+            {
+                for (stat, ns) in iter::zip(STAT_NAMES, stats_ns(samples)) {
+                    value.insert(format!("{phase}{stat}_ns"), ns)?;
+                }
+            }
+            Ok(value)
+        })
+        .collect::<anyhow::Result<_>>()?;
+    println!("{}", simd_json::to_string(&values)?);
+    Ok(())
+}
+
+fn print_csv(records: &[Record]) {
+    let stat_columns = ["parse", "exec"]
+        .into_iter()
+        .flat_map(|phase| STAT_NAMES.map(|stat| format!("{phase}{stat}_ns")))
+        .join(",");
+    println!("day,part,variant,backend,mode,{stat_columns},answer,error");
+
+    for record in records {
+        let stat_fields = [record.parse_times(), record.exec_times()]
+            .into_iter()
+            .flat_map(stats_ns)
+            .map(|ns| ns.map(|ns| ns.to_string()).unwrap_or_default());
+        let fields = [
+            record.day.to_string(),
+            record.part.to_string(),
+            record.variant.to_string(),
+            record.backend.name().to_string(),
+            record.mode.clone(),
+        ]
+        .into_iter()
+        .chain(stat_fields)
+        .chain([
+            record.answer().unwrap_or_default().to_string(),
+            record.error().unwrap_or_default(),
+        ]);
+        println!("{}", fields.map(|field| csv_escape(&field)).join(","));
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
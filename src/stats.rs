@@ -0,0 +1,73 @@
+//! Summary statistics over repeated timing samples.
+
+use std::fmt;
+use std::time::Duration;
+
+/// Timing samples of one phase over repeated runs, sorted in ascending order.
+#[derive(Clone)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is required");
+        samples.sort_unstable();
+        Self(samples)
+    }
+
+    pub fn count(&self) -> usize { self.0.len() }
+
+    pub fn min(&self) -> Duration { self.0[0] }
+
+    pub fn median(&self) -> Duration {
+        let mid = self.0.len() / 2;
+        if self.0.len().is_multiple_of(2) {
+            (self.0[mid - 1] + self.0[mid]) / 2
+        } else {
+            self.0[mid]
+        }
+    }
+
+    pub fn mean(&self) -> Duration { self.0.iter().sum::<Duration>() / self.0.len() as u32 }
+
+    /// The nearest-rank 95th percentile.
+    pub fn p95(&self) -> Duration {
+        let rank = (self.0.len() * 95).div_ceil(100);
+        self.0[rank.max(1) - 1]
+    }
+
+    /// The population standard deviation.
+    pub fn stddev(&self) -> Duration {
+        let mean = self.mean().as_secs_f64();
+        let variance =
+            self.0.iter().map(|sample| (sample.as_secs_f64() - mean).powi(2)).sum::<f64>()
+                / self.0.len() as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
+/// Formats a duration in milliseconds.
+pub struct Millis(pub Duration);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.3}ms", self.0.as_secs_f64() * 1000.)
+    }
+}
+
+impl fmt::Display for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.count() == 1 {
+            return write!(f, "{}", Millis(self.min()));
+        }
+
+        write!(
+            f,
+            "min {}, median {}, mean {}, p95 {}, stddev {}",
+            Millis(self.min()),
+            Millis(self.median()),
+            Millis(self.mean()),
+            Millis(self.p95()),
+            Millis(self.stddev()),
+        )
+    }
+}
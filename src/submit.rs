@@ -0,0 +1,308 @@
+//! Answer submission to the AoC website.
+//!
+//! Every submission with a definite verdict is recorded in `input/d{day}.submissions.txt`
+//! as `{part}: {verdict} {answer}` lines, so that answers known to be wrong are never
+//! submitted again.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::all::{self, SOLUTIONS};
+use crate::{answers, aoc, input_dir, load_input, Mode, Repetition, SubmitArgs};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Verdict {
+    const ALL: &[Self] = &[Self::Correct, Self::TooHigh, Self::TooLow, Self::Wrong];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::Wrong => "wrong",
+        }
+    }
+}
+
+enum Response {
+    Verdict(Verdict),
+    Wait(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Verdict(Verdict::Correct) => write!(f, "correct"),
+            Self::Verdict(Verdict::TooHigh) => write!(f, "incorrect, answer is too high"),
+            Self::Verdict(Verdict::TooLow) => write!(f, "incorrect, answer is too low"),
+            Self::Verdict(Verdict::Wrong) => write!(f, "incorrect"),
+            Self::Wait(duration) => {
+                write!(f, "submitted too recently, wait {} seconds", duration.as_secs())
+            }
+            Self::AlreadySolved => write!(f, "part is already solved or not unlocked yet"),
+        }
+    }
+}
+
+fn parse_response(body: &str) -> anyhow::Result<Response> {
+    if body.contains("That's the right answer") {
+        return Ok(Response::Verdict(Verdict::Correct));
+    }
+
+    if body.contains("That's not the right answer") {
+        let verdict = if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        return Ok(Response::Verdict(verdict));
+    }
+
+    // The wait time is in the sentence `You have 1m 5s left to wait.`
+    if let Some((before, _)) = body.split_once(" left to wait") {
+        if let Some((_, wait)) = before.rsplit_once("You have ") {
+            return Ok(Response::Wait(parse_wait(wait)?));
+        }
+    }
+
+    if body.contains("You don't seem to be solving the right level") {
+        return Ok(Response::AlreadySolved);
+    }
+
+    anyhow::bail!("unrecognized response from answer endpoint:\n{body}")
+}
+
+/// Parses wait times of the form `1m 5s`.
+fn parse_wait(wait: &str) -> anyhow::Result<Duration> {
+    let mut seconds = 0;
+    for token in wait.split_whitespace() {
+        let (value, multiplier) = if let Some(value) = token.strip_suffix('h') {
+            (value, 3600)
+        } else if let Some(value) = token.strip_suffix('m') {
+            (value, 60)
+        } else if let Some(value) = token.strip_suffix('s') {
+            (value, 1)
+        } else {
+            anyhow::bail!("unrecognized wait time {wait:?}");
+        };
+        let value: u64 = value.parse().with_context(|| format!("invalid wait time {wait:?}"))?;
+        seconds += value * multiplier;
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+struct Submission {
+    part:    u32,
+    verdict: Verdict,
+    answer:  String,
+}
+
+fn submissions_path(day: u32) -> anyhow::Result<PathBuf> {
+    Ok(input_dir()?.join(format!("d{day}.submissions.txt")))
+}
+
+fn load_submissions(day: u32) -> anyhow::Result<Vec<Submission>> {
+    let path = submissions_path(day)?;
+    if !fs::exists(&path).with_context(|| format!("test {} existence", path.display()))? {
+        return Ok(Vec::new());
+    }
+
+    let file =
+        fs::read_to_string(&path).with_context(|| format!("read file {}", path.display()))?;
+    file.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_no, line)| {
+            let parse = || {
+                let (part, rest) = line.split_once(':')?;
+                let (verdict, answer) = rest.trim().split_once(' ')?;
+                Some(Submission {
+                    part:    part.trim().parse().ok()?,
+                    verdict: *Verdict::ALL.iter().find(|v| v.name() == verdict)?,
+                    answer:  answer.trim().to_string(),
+                })
+            };
+            parse().with_context(|| {
+                format!("{}:{}: expected `part: verdict answer`", path.display(), line_no + 1)
+            })
+        })
+        .collect()
+}
+
+fn record_submission(day: u32, submission: &Submission) -> anyhow::Result<()> {
+    let path = submissions_path(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("create input directory {}", dir.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("open file {}", path.display()))?;
+    writeln!(file, "{}: {} {}", submission.part, submission.verdict.name(), submission.answer)
+        .with_context(|| format!("write file {}", path.display()))
+}
+
+/// Returns the reason why `answer` is known to be rejected, if any.
+fn known_rejection(submissions: &[Submission], part: u32, answer: &str) -> Option<String> {
+    let numeric = answer.parse::<i128>().ok();
+
+    submissions.iter().filter(|submission| submission.part == part).find_map(|submission| {
+        let previous = &submission.answer;
+        match (submission.verdict, numeric, previous.parse::<i128>().ok()) {
+            (Verdict::Correct, ..) => {
+                Some(format!("part {part} was already solved with {previous}"))
+            }
+            _ if previous == answer => Some(format!("{answer} was already rejected")),
+            (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                Some(format!("{answer} is not lower than {previous}, which was too high"))
+            }
+            (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                Some(format!("{answer} is not higher than {previous}, which was too low"))
+            }
+            _ => None,
+        }
+    })
+}
+
+pub fn run(args: SubmitArgs) -> anyhow::Result<()> {
+    let variant = match &args.variant {
+        Some(name) => name.as_str(),
+        None => {
+            SOLUTIONS
+                .iter()
+                .find(|variant| variant.day == args.day && variant.part == args.part)
+                .with_context(|| format!("No solutions for day {} part {}", args.day, args.part))?
+                .name
+        }
+    };
+
+    let input = load_input(Mode::Private, args.day)?;
+    let outcome = all::solve(args.day, args.part, variant, &input, Repetition::ONCE)?;
+    let answer = outcome.output.trim();
+    println!("Answer from {variant}: {answer}");
+
+    let submissions = load_submissions(args.day)?;
+    if let Some(reason) = known_rejection(&submissions, args.part, answer) {
+        anyhow::bail!("Not submitting: {reason}");
+    }
+
+    let body = aoc::client()?.submit(args.day, args.part, answer)?;
+
+    let response = parse_response(&body)?;
+    println!("Response: {response}");
+
+    if let Response::Verdict(verdict) = response {
+        record_submission(
+            args.day,
+            &Submission { part: args.part, verdict, answer: answer.to_string() },
+        )?;
+
+        if verdict == Verdict::Correct {
+            answers::record(Mode::Private, args.day, args.part, answer)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Saved responses of the answer endpoint.
+    mod pages {
+        pub const CORRECT: &str = include_str!("../tests/fixtures/answer/correct.html");
+        pub const INCORRECT: &str = include_str!("../tests/fixtures/answer/incorrect.html");
+        pub const TOO_HIGH: &str = include_str!("../tests/fixtures/answer/too-high.html");
+        pub const TOO_LOW: &str = include_str!("../tests/fixtures/answer/too-low.html");
+        pub const WAIT: &str = include_str!("../tests/fixtures/answer/wait.html");
+        pub const ALREADY_SOLVED: &str =
+            include_str!("../tests/fixtures/answer/already-solved.html");
+    }
+
+    fn verdict(body: &str) -> Option<Verdict> {
+        match parse_response(body).unwrap() {
+            Response::Verdict(verdict) => Some(verdict),
+            _ => None,
+        }
+    }
+
+    fn wait(body: &str) -> Option<Duration> {
+        match parse_response(body).unwrap() {
+            Response::Wait(wait) => Some(wait),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert!(verdict(pages::CORRECT) == Some(Verdict::Correct));
+        assert!(verdict(pages::INCORRECT) == Some(Verdict::Wrong));
+        assert!(verdict(pages::TOO_HIGH) == Some(Verdict::TooHigh));
+        assert!(verdict(pages::TOO_LOW) == Some(Verdict::TooLow));
+    }
+
+    #[test]
+    fn parse_rate_limit() {
+        assert_eq!(wait(pages::WAIT), Some(Duration::from_secs(65)));
+
+        let body = pages::WAIT.replace("<main>", "<main>\n<p>You have 12 stars.</p>");
+        assert_eq!(wait(&body), Some(Duration::from_secs(65)));
+    }
+
+    #[test]
+    fn parse_already_solved() {
+        assert!(matches!(parse_response(pages::ALREADY_SOLVED).unwrap(), Response::AlreadySolved));
+    }
+
+    #[test]
+    fn parse_unrecognized() {
+        let body = pages::CORRECT.replace("That's the right answer!", "Something else.");
+        assert!(parse_response(&body).is_err());
+    }
+
+    #[test]
+    fn parse_wait_units() {
+        assert_eq!(parse_wait("34s").unwrap(), Duration::from_secs(34));
+        assert_eq!(parse_wait("2h 1m 5s").unwrap(), Duration::from_secs(7265));
+        assert!(parse_wait("a while").is_err());
+    }
+
+    #[test]
+    fn refuse_resubmit() {
+        let submission =
+            |part, verdict, answer: &str| Submission { part, verdict, answer: answer.to_string() };
+        let submissions = [
+            submission(1, Verdict::TooHigh, "500"),
+            submission(1, Verdict::TooLow, "100"),
+            submission(1, Verdict::Wrong, "250"),
+            submission(2, Verdict::Correct, "42"),
+        ];
+
+        for answer in ["250", "500", "600", "100", "50"] {
+            assert!(known_rejection(&submissions, 1, answer).is_some(), "part 1 {answer}");
+        }
+        for answer in ["300", "abc"] {
+            assert!(known_rejection(&submissions, 1, answer).is_none(), "part 1 {answer}");
+        }
+        assert!(known_rejection(&submissions, 2, "43").is_some());
+        assert!(known_rejection(&submissions, 3, "1").is_none());
+    }
+}
@@ -0,0 +1,43 @@
+use std::iter;
+
+use itertools::Itertools;
+
+/// A plain text table with left-aligned columns.
+pub struct Table {
+    header: Vec<&'static str>,
+    rows:   Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: impl IntoIterator<Item = &'static str>) -> Self {
+        Self { header: header.into_iter().collect(), rows: Vec::new() }
+    }
+
+    pub fn push(&mut self, row: impl IntoIterator<Item = String>) {
+        self.rows.push(row.into_iter().collect());
+    }
+
+    pub fn print(&self) {
+        let widths: Vec<usize> = (0..self.header.len())
+            .map(|column| {
+                iter::once(self.header[column].len())
+                    .chain(self.rows.iter().map(|row| row[column].len()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        print_row(self.header.iter().copied(), &widths);
+        print_row(widths.iter().map(|&width| "-".repeat(width)), &widths);
+        for row in &self.rows {
+            print_row(row.iter(), &widths);
+        }
+    }
+}
+
+fn print_row<S: AsRef<str>>(cells: impl Iterator<Item = S>, widths: &[usize]) {
+    let line = iter::zip(cells, widths)
+        .map(|(cell, &width)| format!("{:width$}", cell.as_ref()))
+        .join("  ");
+    println!("{}", line.trim_end());
+}
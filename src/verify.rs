@@ -0,0 +1,102 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::all::{self, TimedOut, SOLUTIONS};
+#[cfg(test)]
+use crate::input_path;
+use crate::{answers, load_input, Mode, Repetition, VerifyArgs};
+
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(anyhow::Error),
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Self::Error(err) => write!(f, "ERROR ({err:#})"),
+            Self::Missing => write!(f, "missing answer"),
+        }
+    }
+}
+
+pub fn run(args: VerifyArgs) -> anyhow::Result<()> {
+    let modes = match args.mode {
+        Some(mode) => &[mode][..],
+        None => Mode::ALL,
+    };
+
+    let timeout = Duration::from_secs(args.timeout);
+    let mut failures = 0;
+    let mut timeouts = 0;
+    for &mode in modes {
+        for variant in SOLUTIONS {
+            let status = match answers::load(mode, variant.day, variant.part)? {
+                None => Status::Missing,
+                Some(expected) => match load_input(mode, variant.day).and_then(|input| {
+                    variant.solve_isolated(input.into(), Repetition::ONCE, timeout)
+                }) {
+                    Ok(outcome) if outcome.output.trim() == expected => Status::Pass,
+                    Ok(outcome) => {
+                        Status::Fail { expected, actual: outcome.output.trim().to_string() }
+                    }
+                    Err(err) => Status::Error(err),
+                },
+            };
+            if let Status::Fail { .. } | Status::Error(_) = status {
+                failures += 1;
+            }
+            if matches!(&status, Status::Error(err) if err.is::<TimedOut>()) {
+                timeouts += 1;
+            }
+
+            println!(
+                "{} day {} part {} {}: {status}",
+                mode.name(),
+                variant.day,
+                variant.part,
+                variant.name
+            );
+        }
+    }
+
+    all::warn_timeouts(timeouts);
+    if failures > 0 {
+        anyhow::bail!("{failures} variants failed verification");
+    }
+    Ok(())
+}
+
+/// Checks a solution variant against the recorded answers, backing the generated tests.
+///
+/// The sample input and answer are required,
+/// while the private input is only checked if both it and its answer are present.
+#[cfg(test)]
+pub(crate) fn check(day: u32, part: u32, name: &str) {
+    let solution = all::find(day, part, name).unwrap();
+
+    for &mode in Mode::ALL {
+        let expected = answers::load(mode, day, part).unwrap();
+        let input_exists = std::fs::exists(input_path(mode, day).unwrap()).unwrap();
+        let expected = match (mode, expected, input_exists) {
+            (_, Some(expected), true) => expected,
+            (Mode::Sample, None, _) => {
+                panic!("no sample answer recorded for day {day} part {part}")
+            }
+            (Mode::Sample, _, false) => panic!("no sample input for day {day}"),
+            (Mode::Private, ..) => continue,
+        };
+
+        let input = load_input(mode, day).unwrap();
+        let outcome = solution
+            .solve(&input, Repetition::ONCE)
+            .unwrap_or_else(|err| panic!("{} input: {err:#}", mode.name()));
+        assert_eq!(outcome.output.trim(), expected, "{} input", mode.name());
+    }
+}
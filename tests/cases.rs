@@ -0,0 +1,51 @@
+//! Runs every variant on the hand-crafted edge cases in `tests/cases/d{day}/`.
+//!
+//! Each case consists of `{name}.input.txt` and `{name}.answers.txt`,
+//! the latter in the same `{part}: {answer}` format as the recorded answers.
+//! Parts without an answer in a case are not checked.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use aoc2024::all::{SolutionDescriptor, SOLUTIONS};
+use aoc2024::{answers, Repetition};
+
+mod common;
+
+/// Runs a variant, describing how it failed if it did not output `expected`.
+fn check(soln: &'static SolutionDescriptor, input: Arc<str>, expected: &str) -> Option<String> {
+    match soln.solve_isolated(input, Repetition::ONCE, Duration::from_secs(60)) {
+        Ok(outcome) if outcome.output.trim() == expected => None,
+        Ok(outcome) => Some(format!("expected {expected}, got {}", outcome.output.trim())),
+        Err(err) => Some(format!("{err:#}")),
+    }
+}
+
+#[test]
+fn edge_cases() {
+    let mut case_count = 0;
+    let mut failures = Vec::new();
+    for (day, input_path) in common::day_files("cases") {
+        let input_path_str = input_path.to_string_lossy();
+        let Some(case) = input_path_str.strip_suffix(".input.txt") else { continue };
+        let case_name =
+            format!("d{day}/{}", Path::new(case).file_name().unwrap().to_string_lossy());
+        let input: Arc<str> = fs::read_to_string(&input_path).unwrap().into();
+        case_count += 1;
+
+        let answers_path = format!("{case}.answers.txt");
+        for (part, expected) in answers::read(Path::new(&answers_path)).unwrap() {
+            let variants = SOLUTIONS.iter().filter(|soln| soln.day == day && soln.part == part);
+            for soln in variants {
+                if let Some(failure) = check(soln, input.clone(), &expected) {
+                    failures.push(format!("{case_name} part {part} {}: {failure}", soln.name));
+                }
+            }
+        }
+    }
+
+    assert!(case_count > 0, "no edge cases found in tests/cases");
+    assert!(failures.is_empty(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+}
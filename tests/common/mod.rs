@@ -0,0 +1,30 @@
+//! Helpers shared by the integration tests.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use aoc2024::all::SOLUTIONS;
+use itertools::Itertools;
+
+/// Lists the files in `tests/{dir}/d{day}/` of each day with solutions as `(day, path)` pairs,
+/// sorted by path within a day.
+///
+/// Days without such a directory are skipped.
+pub fn day_files(dir: &str) -> Vec<(u32, PathBuf)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(dir);
+
+    let mut files = Vec::new();
+    for day in SOLUTIONS.iter().map(|soln| soln.day).dedup() {
+        let day_dir = root.join(format!("d{day}"));
+        let entries = match fs::read_dir(&day_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => panic!("read {}: {err}", day_dir.display()),
+        };
+
+        let mut paths: Vec<_> = entries.map(|entry| entry.unwrap().path()).collect();
+        paths.sort();
+        files.extend(paths.into_iter().map(|path| (day, path)));
+    }
+    files
+}
@@ -0,0 +1,230 @@
+//! Property tests checking that all variants of a part agree on generated inputs.
+//!
+//! Inputs are built from structured strategies rather than seeded generators,
+//! so that a failing case shrinks by dropping lines and simplifying values.
+//! proptest saves the seeds of failing cases in `tests/proptest-regressions/variants.txt`
+//! and reruns them first.
+//! The minimal input can also be saved as `tests/corpus/d{day}/{name}.txt`
+//! so that it is checked on every run.
+
+use std::collections::HashSet;
+use std::fs;
+
+use aoc2024::all::SOLUTIONS;
+use aoc2024::Repetition;
+use itertools::Itertools;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+use proptest::test_runner::FileFailurePersistence;
+
+mod common;
+
+/// Runs all variants of each part of `day` on `input` and reports any disagreement.
+fn check_agreement(day: u32, input: &str) -> Result<(), String> {
+    let variants = SOLUTIONS.iter().filter(|soln| soln.day == day);
+    for (part, variants) in &variants.chunk_by(|soln| soln.part) {
+        let outputs: Vec<(&str, String)> = variants
+            .map(|soln| {
+                let output = match soln.solve(input, Repetition::ONCE) {
+                    Ok(outcome) => outcome.output.trim().to_string(),
+                    Err(err) => format!("error: {err:#}"),
+                };
+                (soln.name, output)
+            })
+            .collect();
+
+        if !outputs.iter().map(|(_, output)| output).all_equal() {
+            let outputs = outputs.iter().map(|(name, output)| format!("{name} = {output}"));
+            return Err(format!("part {part} disagrees: {}", outputs.format(", ")));
+        }
+    }
+    Ok(())
+}
+
+fn check(day: u32, input: &str) -> Result<(), TestCaseError> {
+    check_agreement(day, input)
+        .map_err(|err| TestCaseError::fail(format!("{err}\ninput:\n{input}")))
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// Renders a grid of cells, one row per line.
+fn grid(rows: &[Vec<char>]) -> String { lines(rows.iter().map(|row| row.iter().collect())) }
+
+/// Location ID pairs, mixing small IDs shared between the lists with IDs past 100000.
+fn d1_input() -> impl Strategy<Value = String> {
+    let id = prop_oneof![0..20u32, 0..200_000u32];
+    vec((id.clone(), id), 1..50)
+        .prop_map(|pairs| lines(pairs.into_iter().map(|(l, r)| format!("{l}   {r}"))))
+}
+
+/// Reports of 5 to 8 levels as in real inputs, built from steps that shrink towards 0.
+fn d2_input() -> impl Strategy<Value = String> {
+    let report = (40..60i32, vec(-4..=4i32, 4..8)).prop_map(|(start, steps)| {
+        let levels = steps.iter().scan(start, |level, step| {
+            *level += step;
+            Some(*level)
+        });
+        [start].into_iter().chain(levels).join(" ")
+    });
+    vec(report, 1..20).prop_map(lines)
+}
+
+/// Instructions among fragments of malformed instructions.
+fn d3_input() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        (0..1000u32, 0..1000u32).prop_map(|(a, b)| format!("mul({a},{b})")),
+        Just("do()".to_string()),
+        Just("don't()".to_string()),
+        "[mul(),don't0-9 \n]{1,6}",
+    ];
+    vec(token, 1..30).prop_map(|tokens| tokens.concat() + "\n")
+}
+
+/// A word search grid of the letters of `XMAS`.
+fn d4_input() -> impl Strategy<Value = String> {
+    (1..12usize, 1..12usize)
+        .prop_flat_map(|(width, height)| vec(vec(select(vec!['X', 'M', 'A', 'S']), width), height))
+        .prop_map(|rows| grid(&rows))
+}
+
+/// Rules over a total order of pages, followed by updates of odd length.
+fn d5_input() -> impl Strategy<Value = String> {
+    let pages = Just((10..30u32).collect::<Vec<_>>()).prop_shuffle();
+    pages.prop_flat_map(|pages| {
+        let update = (0..5usize)
+            .prop_flat_map({
+                let pages = pages.clone();
+                move |half| subsequence(pages.clone(), half * 2 + 1).prop_shuffle()
+            })
+            .prop_map(|update| update.iter().join(","));
+        vec(update, 1..10).prop_map(move |updates| {
+            let rules = pages.iter().tuple_combinations().map(|(l, r)| format!("{l}|{r}"));
+            lines(rules) + "\n" + &lines(updates)
+        })
+    })
+}
+
+/// Returns whether a guard starting at `start` facing up leaves the map.
+fn d6_guard_leaves(rows: &[Vec<char>], start: (usize, usize)) -> bool {
+    let (width, height) = (rows[0].len() as isize, rows.len() as isize);
+    let (mut x, mut y) = (start.0 as isize, start.1 as isize);
+    let (mut dx, mut dy) = (0, -1);
+    let mut visited = HashSet::new();
+    while visited.insert((x, y, dx, dy)) {
+        let (nx, ny) = (x + dx, y + dy);
+        if !(0..width).contains(&nx) || !(0..height).contains(&ny) {
+            return true;
+        }
+        if rows[ny as usize][nx as usize] == '#' {
+            (dx, dy) = (-dy, dx);
+        } else {
+            (x, y) = (nx, ny);
+        }
+    }
+    false
+}
+
+/// A lab map with a guard whose patrol leaves the map, since part 1 never ends otherwise.
+fn d6_input() -> impl Strategy<Value = String> {
+    let cell = prop_oneof![4 => Just('.'), 1 => Just('#')];
+    (1..10usize, 1..10usize)
+        .prop_flat_map(move |(width, height)| {
+            (vec(vec(cell.clone(), width), height), 0..width, 0..height)
+        })
+        .prop_map(|(mut rows, x, y)| {
+            rows[y][x] = '^';
+            (rows, (x, y))
+        })
+        .prop_filter("guard must leave the map", |(rows, start)| d6_guard_leaves(rows, *start))
+        .prop_map(|(rows, _)| grid(&rows))
+}
+
+/// Equations whose result is computed from operators, optionally off by one.
+///
+/// Operands are below 100 so that results stay below 10^12 and exact in jq.
+fn d7_input() -> impl Strategy<Value = String> {
+    let equation = (vec((1..100u64, 0..3u8), 1..6), any::<bool>()).prop_map(|(terms, off)| {
+        let result = terms[1..].iter().fold(terms[0].0, |acc, &(operand, op)| match op {
+            0 => acc + operand,
+            1 => acc * operand,
+            _ => acc * 10u64.pow(operand.ilog10() + 1) + operand,
+        });
+        let operands = terms.iter().map(|(operand, _)| operand).join(" ");
+        format!("{}: {operands}", result + u64::from(off))
+    });
+    vec(equation, 1..20).prop_map(lines)
+}
+
+/// A map of antennas of a few frequencies.
+fn d8_input() -> impl Strategy<Value = String> {
+    let cell = prop_oneof![8 => Just('.'), 1 => Just('a'), 1 => Just('b'), 1 => Just('0')];
+    (1..12usize, 1..12usize)
+        .prop_flat_map(move |(width, height)| vec(vec(cell.clone(), width), height))
+        .prop_map(|rows| grid(&rows))
+}
+
+/// A disk map alternating file and free space lengths, ending with a file.
+///
+/// Files are 1 to 9 blocks long as in real inputs.
+fn d9_input() -> impl Strategy<Value = String> {
+    (vec((1..10u8, 0..10u8), 0..20), 1..10u8).prop_map(|(pairs, last)| {
+        let digits = pairs.into_iter().flat_map(|(file, free)| [file, free]).chain([last]);
+        digits.map(|digit| char::from(b'0' + digit)).collect::<String>() + "\n"
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 64,
+        failure_persistence: Some(Box::new(FileFailurePersistence::Direct(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/proptest-regressions/variants.txt"
+        )))),
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn day_1(input in d1_input()) { check(1, &input)?; }
+
+    #[test]
+    fn day_2(input in d2_input()) { check(2, &input)?; }
+
+    #[test]
+    fn day_3(input in d3_input()) { check(3, &input)?; }
+
+    #[test]
+    fn day_4(input in d4_input()) { check(4, &input)?; }
+
+    #[test]
+    fn day_5(input in d5_input()) { check(5, &input)?; }
+
+    #[test]
+    fn day_6(input in d6_input()) { check(6, &input)?; }
+
+    #[test]
+    fn day_7(input in d7_input()) { check(7, &input)?; }
+
+    #[test]
+    fn day_8(input in d8_input()) { check(8, &input)?; }
+
+    #[test]
+    fn day_9(input in d9_input()) { check(9, &input)?; }
+}
+
+/// Checks the saved inputs in `tests/corpus/d{day}/`.
+#[test]
+fn corpus() {
+    let mut failures = Vec::new();
+    for (day, path) in common::day_files("corpus") {
+        let input = fs::read_to_string(&path).unwrap();
+        if let Err(err) = check_agreement(day, &input) {
+            failures.push(format!("{}: {err}", path.display()));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}